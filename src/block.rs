@@ -7,13 +7,15 @@ use sb_sbity::{
     },
     comment::Comment,
     string_hashmap::StringHashMap,
-    value::OpCode,
+    value::Value,
 };
 
 use crate::{
     build_context::TargetContext,
     comment::CommentBuilder,
     custom_block::{CustomBlockBuilder, CustomFuncCallBuilder},
+    opcode::{OpCode, StandardOpCode},
+    registry::{self, BlockShape, InputInfo, InputKind, LabelPart, OpCodeInfo},
     stack::StackBuilder,
     uid::Uid,
 };
@@ -67,6 +69,8 @@ impl BlockInputBuilder {
     ///     .shadow(ShadowInputType::NoShadow)
     ///     .input(Some(StackOrValue::Stack(stack)))
     /// ```
+    ///
+    /// A shadow is put behind it when building, see [`BlockInputBuilder::fill_shadow`].
     pub fn stack(stack: StackBuilder) -> Self {
         let mut b = BlockInputBuilder::new();
         b.set_shadow(ShadowInputType::NoShadow)
@@ -102,6 +106,44 @@ impl BlockInputBuilder {
         b
     }
 
+    /// Input that a block from the palette has, see [`registry::palette_default`].
+    ///
+    /// [`None`] for inputs that are empty in the palette like boolean inputs and `SUBSTACK`s,
+    /// and for blocks that aren't in the [`registry`].
+    pub fn palette_default(opcode: &OpCode, input: &str) -> Option<BlockInputBuilder> {
+        let info = opcode.info()?;
+        Some(match palette_shadow(info, info.input(input)?)? {
+            StackOrValue::Value(value) => BlockInputBuilder::value(value),
+            StackOrValue::Stack(stack) => BlockInputBuilder::shadow_stack(stack),
+        })
+    }
+
+    /// Put a shadow behind a reporter that's alone in this input of `block`,
+    /// like the editor does when a reporter is dropped in an input.
+    /// The shadow is what's left in the input when the reporter is dragged out.
+    ///
+    /// Does nothing when the input already has a shadow, and for boolean inputs and `SUBSTACK`s which don't have one.
+    /// Done automatically when building blocks that are in the [`registry`].
+    pub fn fill_shadow(&mut self, block: &OpCodeInfo, input: &InputInfo) -> &mut Self {
+        let is_lone_reporter = match (&self.shadow, self.values.as_slice()) {
+            (ShadowInputType::NoShadow, [Some(StackOrValue::Stack(stack))]) => {
+                match stack.stack.first() {
+                    Some(BlockBuilder::Normal(n)) => !n.is_shadow(),
+                    Some(_) => true,
+                    None => false,
+                }
+            }
+            _ => false,
+        };
+        if is_lone_reporter {
+            if let Some(shadow) = palette_shadow(block, input) {
+                self.set_shadow(ShadowInputType::ShadowObscured)
+                    .add_input(Some(shadow));
+            }
+        }
+        self
+    }
+
     pub fn build(
         self,
         this_block_uid: &Uid,
@@ -117,19 +159,17 @@ impl BlockInputBuilder {
                 Some(StackOrValue::Stack(s)) => {
                     let first_block_uid = Uid::generate();
                     let mut s_builded = s.build(&first_block_uid, comment_buff, target_context);
-                    let first_block = s_builded.get_mut(&first_block_uid).unwrap();
-                    match first_block {
-                        Block::Normal(n) => {
+                    match s_builded.remove(&first_block_uid).unwrap() {
+                        Block::Normal(mut n) => {
                             n.parent = Some(this_block_uid.clone().into_inner());
                             n.top_level = false;
                             n.x = None;
                             n.y = None;
+                            s_builded.insert(first_block_uid.clone(), Block::Normal(n));
+                            values_b.push(Some(UidOrValue::Uid(first_block_uid.into_inner())))
                         }
-                        Block::VarList(_) => {
-                            let Block::VarList(vl) = s_builded.remove(&first_block_uid).unwrap()
-                            else {
-                                unreachable!()
-                            };
+                        // Variable and list reporters are put directly in the input
+                        Block::VarList(vl) => {
                             let BlockVarListReporterTop { kind, name, id, .. } = vl;
                             values_b.push(Some(UidOrValue::Value(match kind {
                                 ListOrVariable::Variable => BlockInputValue::Variable { name, id },
@@ -138,7 +178,6 @@ impl BlockInputBuilder {
                         }
                     }
                     final_stack.extend(s_builded);
-                    values_b.push(Some(UidOrValue::Uid(first_block_uid.into_inner())))
                 }
                 None => values_b.push(None),
             }
@@ -150,6 +189,42 @@ impl BlockInputBuilder {
     }
 }
 
+/// Shadow that this input of `block` has in the palette
+fn palette_shadow(block: &OpCodeInfo, input: &InputInfo) -> Option<StackOrValue> {
+    let text: Value = block
+        .palette_default(input.name)
+        .unwrap_or_default()
+        .to_owned()
+        .into();
+    let value = match input.kind {
+        InputKind::Number => BlockInputValue::Number { value: text },
+        InputKind::PositiveNumber => BlockInputValue::PositiveNumber { value: text },
+        InputKind::WholeNumber => BlockInputValue::PositiveInteger { value: text },
+        InputKind::Integer => BlockInputValue::Integer { value: text },
+        InputKind::Angle => BlockInputValue::Angle { value: text },
+        InputKind::Color => BlockInputValue::Color {
+            value: "#000000".to_owned().into(),
+        },
+        InputKind::Text => BlockInputValue::String { value: text },
+        InputKind::Menu(menu) => {
+            // Menus without a palette default start on their first option, or empty if there's none
+            let menu = registry::lookup(menu)?;
+            let field = menu.fields.first()?;
+            let mut b = BlockNormalBuilder::new(menu.opcode);
+            b.add_field(
+                field.name,
+                BlockFieldBuilder::palette_default(b.opcode(), field.name)
+                    .unwrap_or_else(|| BlockFieldBuilder::new(String::new())),
+            )
+            .set_shadow(true);
+            return Some(StackOrValue::Stack(StackBuilder::start(b)));
+        }
+        // Broadcast needs the id of a broadcast that might not exist
+        InputKind::Broadcast | InputKind::Boolean | InputKind::SubStack => return None,
+    };
+    Some(StackOrValue::Value(value))
+}
+
 impl Default for BlockInputBuilder {
     fn default() -> Self {
         BlockInputBuilder {
@@ -159,6 +234,47 @@ impl Default for BlockInputBuilder {
     }
 }
 
+/// Text input
+impl From<&str> for BlockInputBuilder {
+    fn from(value: &str) -> Self {
+        BlockInputBuilder::value(BlockInputValue::String {
+            value: value.to_owned().into(),
+        })
+    }
+}
+
+/// Text input
+impl From<String> for BlockInputBuilder {
+    fn from(value: String) -> Self {
+        BlockInputBuilder::value(BlockInputValue::String {
+            value: value.into(),
+        })
+    }
+}
+
+/// Number input
+impl From<f64> for BlockInputBuilder {
+    fn from(value: f64) -> Self {
+        BlockInputBuilder::value(BlockInputValue::Number {
+            value: value.into(),
+        })
+    }
+}
+
+/// Number input
+impl From<i32> for BlockInputBuilder {
+    fn from(value: i32) -> Self {
+        BlockInputBuilder::from(f64::from(value))
+    }
+}
+
+/// Reporter put in the input, same as [`BlockInputBuilder::stack`]
+impl From<StackBuilder> for BlockInputBuilder {
+    fn from(stack: StackBuilder) -> Self {
+        BlockInputBuilder::stack(stack)
+    }
+}
+
 /// Raw block creation
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct BlockNormalBuilder {
@@ -189,6 +305,23 @@ impl BlockNormalBuilder {
         self
     }
 
+    /// Add `input`, or the input the block has in the palette when it's [`None`].
+    /// See [`BlockInputBuilder::palette_default`].
+    pub fn add_input_or_default<K: Into<String>, I: Into<Option<BlockInputBuilder>>>(
+        &mut self,
+        key: K,
+        input: I,
+    ) -> &mut Self {
+        let key = key.into();
+        let input = input
+            .into()
+            .or_else(|| BlockInputBuilder::palette_default(&self.opcode, &key));
+        if let Some(input) = input {
+            self.inputs.insert(key, input);
+        }
+        self
+    }
+
     pub fn add_field<S: Into<String>>(
         &mut self,
         key: S,
@@ -198,8 +331,58 @@ impl BlockNormalBuilder {
         self
     }
 
-    pub fn set_opcode(&mut self, opcode: OpCode) -> &mut Self {
-        self.opcode = opcode;
+    /// Add `field`, or the option the block's menu starts on in the palette when it's [`None`].
+    /// See [`BlockFieldBuilder::palette_default`].
+    pub fn add_field_or_default<K: Into<String>, F: Into<Option<BlockFieldBuilder>>>(
+        &mut self,
+        key: K,
+        field: F,
+    ) -> &mut Self {
+        let key = key.into();
+        let field = field
+            .into()
+            .or_else(|| BlockFieldBuilder::palette_default(&self.opcode, &key));
+        if let Some(field) = field {
+            self.fields.insert(key, field);
+        }
+        self
+    }
+
+    pub fn opcode(&self) -> &OpCode {
+        &self.opcode
+    }
+
+    pub fn inputs(&self) -> &HashMap<String, BlockInputBuilder> {
+        &self.inputs
+    }
+
+    pub fn fields(&self) -> &HashMap<String, BlockFieldBuilder> {
+        &self.fields
+    }
+
+    pub fn is_shadow(&self) -> bool {
+        self.shadow
+    }
+
+    /// Shape from the [`registry`], except for `control_stop` which is only a cap
+    /// when it stops the script it's in.
+    pub fn shape(&self) -> Option<BlockShape> {
+        let shape = self.opcode.info()?.shape;
+        if self.opcode == OpCode::Standard(StandardOpCode::control_stop) {
+            let option = self.fields.get("STOP_OPTION").map(|f| f.value.as_str());
+            if let Some("other scripts in sprite" | "other scripts in stage") = option {
+                return Some(BlockShape::Stack);
+            }
+        }
+        Some(shape)
+    }
+
+    pub fn pos(&self) -> (Option<f64>, Option<f64>) {
+        (self.x, self.y)
+    }
+
+    pub fn set_opcode<O: Into<OpCode>>(&mut self, opcode: O) -> &mut Self {
+        self.opcode = opcode.into();
         self
     }
 
@@ -208,6 +391,14 @@ impl BlockNormalBuilder {
         self
     }
 
+    pub fn comment_mut(&mut self) -> Option<&mut CommentBuilder> {
+        self.comment.as_mut()
+    }
+
+    pub fn inputs_mut(&mut self) -> &mut HashMap<String, BlockInputBuilder> {
+        &mut self.inputs
+    }
+
     pub fn set_inputs(&mut self, inputs: HashMap<String, BlockInputBuilder>) -> &mut Self {
         self.inputs = inputs;
         self
@@ -265,9 +456,15 @@ impl BlockNormalBuilder {
         // for (key, input) in inputs {
         //     inputs_b.insert(key, input.build(comment_buff, final_stack, &my_uid));
         // }
+        let info = opcode.info();
         let inputs: HashMap<String, BlockInput> = inputs
             .into_iter()
-            .map(|(key, input)| {
+            .map(|(key, mut input)| {
+                if let Some(info) = info {
+                    if let Some(input_info) = info.input(&key) {
+                        input.fill_shadow(info, input_info);
+                    }
+                }
                 (
                     key,
                     input.build(my_uid, comment_buff, final_stack, target_context),
@@ -290,7 +487,7 @@ impl BlockNormalBuilder {
         };
 
         BlockNormal {
-            opcode,
+            opcode: opcode.into(),
             comment,
             next: None,
             parent: None,
@@ -335,6 +532,19 @@ impl BlockFieldBuilder {
         }
     }
 
+    /// Option that the field's menu starts on in the palette, see [`registry::palette_default`].
+    /// Menus that aren't in there start on their first option.
+    ///
+    /// [`None`] for fields that start empty or need a name, like variables,
+    /// and for blocks that aren't in the [`registry`].
+    pub fn palette_default(opcode: &OpCode, field: &str) -> Option<BlockFieldBuilder> {
+        let info = opcode.info()?;
+        let option = info
+            .palette_default(field)
+            .or_else(|| info.field(field)?.menu.options().first().copied())?;
+        Some(BlockFieldBuilder::new(option.to_owned()))
+    }
+
     pub fn set_value(&mut self, value: String) -> &mut Self {
         self.value = value;
         self
@@ -354,13 +564,12 @@ impl BlockFieldBuilder {
             FieldKind::NoRef => return BlockField::NoId { value },
             FieldKind::NoRefMaybe => return BlockField::WithId { value, id: None },
 
-            FieldKind::Broadcast => target_context.all_broadcasts,
-            FieldKind::SpriteVariable => target_context.this_sprite_vars,
-            FieldKind::GlobalVariable => target_context.global_vars,
-            FieldKind::SpriteList => target_context.this_sprite_lists,
-            FieldKind::GlobalList => target_context.global_lists,
+            FieldKind::Broadcast => target_context.all_broadcasts.get(value_str),
+            FieldKind::SpriteVariable => target_context.sprite_var(value_str),
+            FieldKind::GlobalVariable => target_context.global_vars.get(value_str),
+            FieldKind::SpriteList => target_context.sprite_list(value_str),
+            FieldKind::GlobalList => target_context.global_lists.get(value_str),
         }
-        .get(value_str)
         .cloned()
         .unwrap_or_else(|| Uid::new("__unknown__"));
         BlockField::WithId {
@@ -381,8 +590,8 @@ pub struct BlockVarListBuilder {
     pub kind: ListOrVariable,
     pub from: VarListFrom,
     pub name: String,
-    pub x: f64,
-    pub y: f64,
+    pub x: Option<f64>,
+    pub y: Option<f64>,
     pub comment: Option<CommentBuilder>,
 }
 
@@ -392,8 +601,8 @@ impl BlockVarListBuilder {
             kind: ListOrVariable::Variable,
             name: name.into(),
             from: VarListFrom::Global,
-            x: 0.,
-            y: 0.,
+            x: None,
+            y: None,
             comment: None,
         }
     }
@@ -403,8 +612,8 @@ impl BlockVarListBuilder {
             kind: ListOrVariable::List,
             name: name.into(),
             from: VarListFrom::Global,
-            x: 0.,
-            y: 0.,
+            x: None,
+            y: None,
             comment: None,
         }
     }
@@ -414,8 +623,8 @@ impl BlockVarListBuilder {
             kind: ListOrVariable::Variable,
             from: VarListFrom::Sprite,
             name: name.into(),
-            x: 0.,
-            y: 0.,
+            x: None,
+            y: None,
             comment: None,
         }
     }
@@ -425,8 +634,8 @@ impl BlockVarListBuilder {
             kind: ListOrVariable::List,
             from: VarListFrom::Sprite,
             name: name.into(),
-            x: 0.,
-            y: 0.,
+            x: None,
+            y: None,
             comment: None,
         }
     }
@@ -447,18 +656,18 @@ impl BlockVarListBuilder {
     }
 
     pub fn set_x(&mut self, x: f64) -> &mut Self {
-        self.x = x;
+        self.x = Some(x);
         self
     }
 
     pub fn set_y(&mut self, y: f64) -> &mut Self {
-        self.y = y;
+        self.y = Some(y);
         self
     }
 
     pub fn set_pos(&mut self, x: f64, y: f64) -> &mut Self {
-        self.x = x;
-        self.y = y;
+        self.x = Some(x);
+        self.y = Some(y);
         self
     }
 
@@ -467,12 +676,11 @@ impl BlockVarListBuilder {
         self
     }
 
-    pub fn build(
-        self,
-        my_uid: &Uid,
-        comment_buff: &mut HashMap<Uid, Comment>,
-        target_context: &TargetContext,
-    ) -> BlockVarListReporterTop {
+    /// The same reporter as a `data_variable` or `data_listcontents` block.
+    ///
+    /// Reporters in the short form of the sb3 format can't have a comment,
+    /// [`BlockBuilder::build`] uses this for reporters that has one.
+    pub fn into_normal(self) -> BlockNormalBuilder {
         let BlockVarListBuilder {
             kind,
             from,
@@ -481,28 +689,65 @@ impl BlockVarListBuilder {
             y,
             comment,
         } = self;
+        let (opcode, field_name, field_kind) = match (kind, from) {
+            (ListOrVariable::Variable, VarListFrom::Global) => (
+                StandardOpCode::data_variable,
+                "VARIABLE",
+                FieldKind::GlobalVariable,
+            ),
+            (ListOrVariable::Variable, VarListFrom::Sprite) => (
+                StandardOpCode::data_variable,
+                "VARIABLE",
+                FieldKind::SpriteVariable,
+            ),
+            (ListOrVariable::List, VarListFrom::Global) => (
+                StandardOpCode::data_listcontents,
+                "LIST",
+                FieldKind::GlobalList,
+            ),
+            (ListOrVariable::List, VarListFrom::Sprite) => (
+                StandardOpCode::data_listcontents,
+                "LIST",
+                FieldKind::SpriteList,
+            ),
+        };
+        let mut b = BlockNormalBuilder::new(opcode);
+        b.add_field(
+            field_name,
+            BlockFieldBuilder::new_with_kind(name, field_kind),
+        )
+        .set_comment(comment)
+        .set_pos(x, y);
+        b
+    }
+
+    /// Comment is ignored, see [`BlockVarListBuilder::into_normal`].
+    pub fn build(self, target_context: &TargetContext) -> BlockVarListReporterTop {
+        let BlockVarListBuilder {
+            kind,
+            from,
+            name,
+            x,
+            y,
+            comment: _,
+        } = self;
         let varlist_id = match (&kind, from) {
-            (ListOrVariable::Variable, VarListFrom::Global) => target_context.global_vars,
-            (ListOrVariable::Variable, VarListFrom::Sprite) => target_context.this_sprite_vars,
-            (ListOrVariable::List, VarListFrom::Global) => target_context.global_lists,
-            (ListOrVariable::List, VarListFrom::Sprite) => target_context.this_sprite_lists,
+            (ListOrVariable::Variable, VarListFrom::Global) => {
+                target_context.global_vars.get(&name)
+            }
+            (ListOrVariable::Variable, VarListFrom::Sprite) => target_context.sprite_var(&name),
+            (ListOrVariable::List, VarListFrom::Global) => target_context.global_lists.get(&name),
+            (ListOrVariable::List, VarListFrom::Sprite) => target_context.sprite_list(&name),
         }
-        .get(&name)
         .cloned()
         .unwrap_or(Uid::new("__unknown__"));
-        if let Some(comment) = comment {
-            let comment_uid = Uid::generate();
-            let mut comment = comment.build();
-            comment.block_id = Some(my_uid.clone().into_inner());
-            comment_buff.insert(comment_uid, comment);
-        }
 
         BlockVarListReporterTop {
             kind,
             name,
             id: varlist_id.into_inner(),
-            x: x.into(),
-            y: y.into(),
+            x: x.unwrap_or_default().into(),
+            y: y.unwrap_or_default().into(),
         }
     }
 }
@@ -516,6 +761,25 @@ pub enum BlockBuilder {
 }
 
 impl BlockBuilder {
+    /// Shape from the [`registry`](crate::registry), [`None`] if the opcode isn't in it
+    pub fn shape(&self) -> Option<BlockShape> {
+        match self {
+            BlockBuilder::Normal(n) => n.shape(),
+            BlockBuilder::CustomBlock(_) => Some(BlockShape::Hat),
+            BlockBuilder::CustomBlockCall(_) => Some(BlockShape::Stack),
+            BlockBuilder::VarList(_) => Some(BlockShape::Reporter),
+        }
+    }
+
+    pub fn comment_mut(&mut self) -> Option<&mut CommentBuilder> {
+        match self {
+            BlockBuilder::Normal(n) => n.comment_mut(),
+            BlockBuilder::CustomBlock(c) => c.comment_mut(),
+            BlockBuilder::CustomBlockCall(c) => c.comment_mut(),
+            BlockBuilder::VarList(vl) => vl.comment.as_mut(),
+        }
+    }
+
     pub fn build(
         self,
         my_uid: &Uid,
@@ -532,8 +796,14 @@ impl BlockBuilder {
                 let b = f.build(my_uid, comment_buff, final_stack, target_context);
                 Block::Normal(b)
             }
+            BlockBuilder::VarList(vl) if vl.comment.is_some() => {
+                let b = vl
+                    .into_normal()
+                    .build(my_uid, comment_buff, final_stack, target_context);
+                Block::Normal(b)
+            }
             BlockBuilder::VarList(vl) => {
-                let b = vl.build(my_uid, comment_buff, target_context);
+                let b = vl.build(target_context);
                 Block::VarList(b)
             }
             BlockBuilder::CustomBlockCall(fc) => {
@@ -574,17 +844,13 @@ impl BlockBuilder {
         match self {
             BlockBuilder::Normal(n) => {
                 if !is_input {
-                    let flag_opcodes = |opcode: &OpCode| -> bool {
-                        if opcode == "control_start_as_clone" {
-                            return true;
-                        }
-                        if opcode.starts_with("event_when") {
-                            return true;
-                        }
-                        return false;
-                    };
+                    let is_hat = n
+                        .opcode
+                        .info()
+                        .map(|info| info.shape.is_hat())
+                        .unwrap_or(false);
 
-                    if flag_opcodes(&n.opcode) {
+                    if is_hat {
                         return data.event_block_height;
                     }
                 }
@@ -626,4 +892,93 @@ impl BlockBuilder {
             BlockBuilder::VarList(_) => data.input_block_height,
         }
     }
+
+    /// Width of an input, as if it's the only thing in the block
+    fn input_width(data: &crate::stack::BlockWidthData, input: Option<&BlockInputBuilder>) -> f64 {
+        let width = input
+            .and_then(|input| input.values.iter().flatten().next())
+            .map(|value| match value {
+                StackOrValue::Stack(stack) => stack.calc_block_width(data),
+                StackOrValue::Value(value) => {
+                    input_value_len(value) as f64 * data.char_width + data.input_padding
+                }
+            })
+            .unwrap_or(0.);
+        width.max(data.min_input_width)
+    }
+
+    pub fn calc_block_width(&self, data: &crate::stack::BlockWidthData) -> f64 {
+        match self {
+            BlockBuilder::Normal(n) => {
+                let row_width = match n.opcode.info() {
+                    Some(info) => info
+                        .label_parts()
+                        .map(|part| match part {
+                            LabelPart::Text(text) => text.len() as f64 * data.char_width,
+                            LabelPart::Slot(name) => match n.fields.get(name) {
+                                Some(field) => {
+                                    field.value.len() as f64 * data.char_width + data.dropdown_width
+                                }
+                                None if info.field(name).is_some() => data.min_input_width,
+                                None => Self::input_width(data, n.inputs.get(name)),
+                            },
+                        })
+                        .sum::<f64>(),
+                    None => {
+                        n.opcode.as_str().len() as f64 * data.char_width
+                            + n.fields
+                                .values()
+                                .map(|field| {
+                                    field.value.len() as f64 * data.char_width + data.dropdown_width
+                                })
+                                .sum::<f64>()
+                            + n.inputs
+                                .iter()
+                                .filter(|(name, _)| !name.starts_with("SUBSTACK"))
+                                .map(|(_, input)| Self::input_width(data, Some(input)))
+                                .sum::<f64>()
+                    }
+                } + data.block_padding;
+                let nested_width = n
+                    .inputs
+                    .iter()
+                    .filter(|(name, _)| name.starts_with("SUBSTACK"))
+                    .flat_map(|(_, input)| &input.values)
+                    .filter_map(|value| match value {
+                        Some(StackOrValue::Stack(stack)) => {
+                            Some(data.nest_indent + stack.calc_block_width(data))
+                        }
+                        _ => None,
+                    })
+                    .fold(0., f64::max);
+                row_width.max(nested_width)
+            }
+            BlockBuilder::CustomBlock(c) => {
+                ("define ".len() + c.proccode().len()) as f64 * data.char_width + data.block_padding
+            }
+            BlockBuilder::CustomBlockCall(c) => {
+                c.proccode().len() as f64 * data.char_width
+                    + c.args
+                        .iter()
+                        .map(|(_, input)| Self::input_width(data, Some(input)))
+                        .sum::<f64>()
+                    + data.block_padding
+            }
+            BlockBuilder::VarList(vl) => {
+                vl.name.len() as f64 * data.char_width + data.input_padding
+            }
+        }
+    }
+}
+
+/// Amount of characters shown in an input
+fn input_value_len(value: &BlockInputValue) -> usize {
+    match value {
+        BlockInputValue::Number { value } => serde_json::to_string(value).map_or(0, |s| s.len()),
+        BlockInputValue::String { value } => serde_json::to_string(value).map_or(0, |s| s.len()),
+        BlockInputValue::Broadcast { name, .. }
+        | BlockInputValue::Variable { name, .. }
+        | BlockInputValue::List { name, .. } => name.len(),
+        _ => 0,
+    }
 }
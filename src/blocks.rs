@@ -4,6 +4,11 @@
 //! It's not required to be use in function argument in here
 //! which might introduce some invalid argument to function that normally requires a menu in the editor.
 //!
+//! Inputs that take `impl Into<Option<Bib>>` can be given [`None`] to get what the block has
+//! when it's dragged out of the palette, `move_steps(None)` moves 10 steps.
+//! Menus that take `impl Into<Option<Bfb>>` work the same, `go_to_layer(None)` goes to the front.
+//! See [`registry::palette_default`](crate::registry::palette_default).
+//!
 //! Some reserved input (you shouldn't try to name anything with thing in this list):
 //!  - `"_random_"`
 //!  - `"_mouse_"`
@@ -13,6 +18,9 @@
 //!  - `"_stage_"`
 //!
 
+pub mod control_flow;
+pub mod video_sensing;
+
 use std::collections::HashMap;
 
 use crate::{
@@ -38,18 +46,18 @@ type Bfb = BlockFieldBuilder;
 type Bib = BlockInputBuilder;
 
 // Control =====================================================================
-pub fn wait(duration: Bib) -> StackBuilder {
+pub fn wait(duration: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::control_wait);
-        b.add_input("DURATION", duration);
+        b.add_input_or_default("DURATION", duration);
         b
     })
 }
 
-pub fn repeat(times: Bib, to_repeat: Option<Bib>) -> StackBuilder {
+pub fn repeat(times: impl Into<Option<Bib>>, to_repeat: Option<Bib>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::control_repeat);
-        b.add_input("TIMES", times);
+        b.add_input_or_default("TIMES", times);
         if let Some(to_repeat) = to_repeat {
             b.add_input("SUBSTACK", to_repeat);
         }
@@ -102,7 +110,7 @@ pub fn wait_until(condition: Bib) -> StackBuilder {
 
 pub fn repeat_until(condition: Bib, to_repeat: Option<Bib>) -> StackBuilder {
     StackBuilder::start({
-        let mut b = BlockNormalBuilder::new(StandardOpCode::control_if_else);
+        let mut b = BlockNormalBuilder::new(StandardOpCode::control_repeat_until);
         b.add_input("CONDITION", condition);
         if let Some(to_repeat) = to_repeat {
             b.add_input("SUBSTACK", to_repeat);
@@ -115,11 +123,12 @@ pub fn repeat_until(condition: Bib, to_repeat: Option<Bib>) -> StackBuilder {
 /// `stop_option` Accepts:
 ///  - `"this script"` and `has_next` should be `false`
 ///  - `"other scripts in sprite"` and `has_next` should be `true`
+///  - `"other scripts in stage"` and `has_next` should be `true`
 ///  - `"all"` and `has_next` should be `false`
-pub fn stop(stop_option: Bfb, has_next: bool) -> StackBuilder {
+pub fn stop(stop_option: impl Into<Option<Bfb>>, has_next: bool) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::control_stop);
-        b.add_field("STOP_OPTION", stop_option)
+        b.add_field_or_default("STOP_OPTION", stop_option)
             .set_mutation(BlockMutation {
                 tag_name: "mutation".to_owned(),
                 children: vec![],
@@ -138,10 +147,10 @@ pub fn when_i_start_as_a_clone() -> StackBuilder {
 /// <br/>
 /// Accepts:
 ///  - Sprite name
-pub fn create_clone_of(sprite: Bib) -> StackBuilder {
+pub fn create_clone_of(sprite: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::control_create_clone_of);
-        b.add_input("CLONE_OPTION", sprite);
+        b.add_input_or_default("CLONE_OPTION", sprite);
         b
     })
 }
@@ -150,10 +159,11 @@ pub fn create_clone_of(sprite: Bib) -> StackBuilder {
 ///
 /// Accepts:
 ///  - Sprite name
-pub fn create_clone_of_menu(sprite: Bfb) -> StackBuilder {
+pub fn create_clone_of_menu(sprite: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
-        let mut b = BlockNormalBuilder::new(StandardOpCode::control_create_clone_of);
-        b.add_field("CLONE_OPTION", sprite).set_shadow(true);
+        let mut b = BlockNormalBuilder::new(StandardOpCode::control_create_clone_of_menu);
+        b.add_field_or_default("CLONE_OPTION", sprite)
+            .set_shadow(true);
         b
     })
 }
@@ -164,6 +174,70 @@ pub fn delete_this_clone() -> StackBuilder {
     ))
 }
 
+/// Hidden block, see [`Validator::allow_hidden_blocks`](crate::validate::Validator::allow_hidden_blocks)
+///
+/// Repeat while `condition` is true.
+pub fn while_(condition: Bib, to_repeat: Option<Bib>) -> StackBuilder {
+    StackBuilder::start({
+        let mut b = BlockNormalBuilder::new(StandardOpCode::control_while);
+        b.add_input("CONDITION", condition);
+        if let Some(to_repeat) = to_repeat {
+            b.add_input("SUBSTACK", to_repeat);
+        }
+        b
+    })
+}
+
+/// Hidden block, see [`Validator::allow_hidden_blocks`](crate::validate::Validator::allow_hidden_blocks)
+///
+/// Set `var` to 1, 2, ... up to `value` and run `to_repeat` each time.
+/// The loop index is kept by the loop itself, so changing `var` inside `to_repeat` doesn't affect the iteration.
+pub fn for_each(var: Bfb, value: impl Into<Option<Bib>>, to_repeat: Option<Bib>) -> StackBuilder {
+    StackBuilder::start({
+        let mut b = BlockNormalBuilder::new(StandardOpCode::control_for_each);
+        b.add_input_or_default("VALUE", value)
+            .add_field("VARIABLE", var);
+        if let Some(to_repeat) = to_repeat {
+            b.add_input("SUBSTACK", to_repeat);
+        }
+        b
+    })
+}
+
+/// Hidden block, see [`Validator::allow_hidden_blocks`](crate::validate::Validator::allow_hidden_blocks)
+///
+/// The counter is shared by every sprite in the project.
+pub fn get_counter() -> StackBuilder {
+    StackBuilder::start(BlockNormalBuilder::new(StandardOpCode::control_get_counter))
+}
+
+/// Hidden block, see [`Validator::allow_hidden_blocks`](crate::validate::Validator::allow_hidden_blocks)
+pub fn incr_counter() -> StackBuilder {
+    StackBuilder::start(BlockNormalBuilder::new(
+        StandardOpCode::control_incr_counter,
+    ))
+}
+
+/// Hidden block, see [`Validator::allow_hidden_blocks`](crate::validate::Validator::allow_hidden_blocks)
+pub fn clear_counter() -> StackBuilder {
+    StackBuilder::start(BlockNormalBuilder::new(
+        StandardOpCode::control_clear_counter,
+    ))
+}
+
+/// Hidden block, see [`Validator::allow_hidden_blocks`](crate::validate::Validator::allow_hidden_blocks)
+///
+/// Scratch 3 runs this just like a normal stack, it doesn't actually run `to_run` in one frame.
+pub fn all_at_once(to_run: Option<Bib>) -> StackBuilder {
+    StackBuilder::start({
+        let mut b = BlockNormalBuilder::new(StandardOpCode::control_all_at_once);
+        if let Some(to_run) = to_run {
+            b.add_input("SUBSTACK", to_run);
+        }
+        b
+    })
+}
+
 // Event =======================================================================
 pub fn when_flag_clicked() -> StackBuilder {
     StackBuilder::start(BlockNormalBuilder::new(
@@ -181,10 +255,10 @@ pub fn when_flag_clicked() -> StackBuilder {
 ///  - `"down arrow"`
 ///  - Number 0 - 9
 ///  - Letter a - z
-pub fn when_key_pressed(key: Bfb) -> StackBuilder {
+pub fn when_key_pressed(key: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::event_whenkeypressed);
-        b.add_field("KEY_OPTION", key);
+        b.add_field_or_default("KEY_OPTION", key);
         b
     })
 }
@@ -198,10 +272,10 @@ pub fn when_this_sprite_clicked() -> StackBuilder {
 /// <br/>
 /// Accepts:
 ///  - Backdrop name
-pub fn when_backdrop_switches_to(backdrop: Bfb) -> StackBuilder {
+pub fn when_backdrop_switches_to(backdrop: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::event_whenbackdropswitchesto);
-        b.add_field("BACKDROP", backdrop);
+        b.add_field_or_default("BACKDROP", backdrop);
         b
     })
 }
@@ -210,11 +284,14 @@ pub fn when_backdrop_switches_to(backdrop: Bfb) -> StackBuilder {
 /// Accepts:
 /// - `"LOUDNESS"`
 /// - `"TIMER"`
-pub fn when_greater_than(variable: Bfb, value: Bib) -> StackBuilder {
+pub fn when_greater_than(
+    variable: impl Into<Option<Bfb>>,
+    value: impl Into<Option<Bib>>,
+) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::event_whengreaterthan);
-        b.add_input("VALUE", value)
-            .add_field("WHENGREATERTHANMENU", variable);
+        b.add_input_or_default("VALUE", value)
+            .add_field_or_default("WHENGREATERTHANMENU", variable);
         b
     })
 }
@@ -244,34 +321,39 @@ pub fn broadcast_and_wait(broadcast: Bib) -> StackBuilder {
 }
 
 // Looks =======================================================================
-pub fn think(message: Bib) -> StackBuilder {
+pub fn think(message: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::looks_think);
-        b.add_input("MESSAGE", message);
+        b.add_input_or_default("MESSAGE", message);
         b
     })
 }
 
-pub fn think_for_secs(message: Bib, secs: Bib) -> StackBuilder {
+pub fn think_for_secs(
+    message: impl Into<Option<Bib>>,
+    secs: impl Into<Option<Bib>>,
+) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::looks_thinkforsecs);
-        b.add_input("MESSAGE", message).add_input("SECS", secs);
+        b.add_input_or_default("MESSAGE", message)
+            .add_input_or_default("SECS", secs);
         b
     })
 }
 
-pub fn say(message: Bib) -> StackBuilder {
+pub fn say(message: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::looks_say);
-        b.add_input("MESSAGE", message);
+        b.add_input_or_default("MESSAGE", message);
         b
     })
 }
 
-pub fn say_for_secs(message: Bib, secs: Bib) -> StackBuilder {
+pub fn say_for_secs(message: impl Into<Option<Bib>>, secs: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::looks_sayforsecs);
-        b.add_input("MESSAGE", message).add_input("SECS", secs);
+        b.add_input_or_default("MESSAGE", message)
+            .add_input_or_default("SECS", secs);
         b
     })
 }
@@ -279,10 +361,10 @@ pub fn say_for_secs(message: Bib, secs: Bib) -> StackBuilder {
 /// <br/>
 /// Accepts:
 ///  - Costume name
-pub fn switch_costume_to(costume: Bib) -> StackBuilder {
+pub fn switch_costume_to(costume: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::looks_switchcostumeto);
-        b.add_input("COSTUME", costume);
+        b.add_input_or_default("COSTUME", costume);
         b
     })
 }
@@ -291,10 +373,10 @@ pub fn switch_costume_to(costume: Bib) -> StackBuilder {
 ///
 /// Accepts:
 ///  - Costume name
-pub fn costume_menu(costume: Bfb) -> StackBuilder {
+pub fn costume_menu(costume: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::looks_costume);
-        b.add_field("COSTUME", costume).set_shadow(true);
+        b.add_field_or_default("COSTUME", costume).set_shadow(true);
         b
     })
 }
@@ -306,10 +388,10 @@ pub fn next_costume() -> StackBuilder {
 /// <br/>
 /// Accepts:
 ///  - Backdrop name
-pub fn switch_backdrop_to(backdrop: Bib) -> StackBuilder {
+pub fn switch_backdrop_to(backdrop: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::looks_switchbackdropto);
-        b.add_input("BACKDROP", backdrop);
+        b.add_input_or_default("BACKDROP", backdrop);
         b
     })
 }
@@ -318,10 +400,11 @@ pub fn switch_backdrop_to(backdrop: Bib) -> StackBuilder {
 ///
 /// Accepts:
 ///  - Backdrop name
-pub fn backdrop_menu(backdrop: Bfb) -> StackBuilder {
+pub fn backdrop_menu(backdrop: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::looks_backdrops);
-        b.add_field("BACKDROP", backdrop).set_shadow(true);
+        b.add_field_or_default("BACKDROP", backdrop)
+            .set_shadow(true);
         b
     })
 }
@@ -330,18 +413,18 @@ pub fn next_backdrop() -> StackBuilder {
     StackBuilder::start(BlockNormalBuilder::new(StandardOpCode::looks_nextbackdrop))
 }
 
-pub fn change_size_by(by: Bib) -> StackBuilder {
+pub fn change_size_by(by: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::looks_changesizeby);
-        b.add_input("CHANGE", by);
+        b.add_input_or_default("CHANGE", by);
         b
     })
 }
 
-pub fn set_size_to(to: Bib) -> StackBuilder {
+pub fn set_size_to(to: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::looks_setsizeto);
-        b.add_input("SIZE", to);
+        b.add_input_or_default("SIZE", to);
 
         b
     })
@@ -356,10 +439,14 @@ pub fn set_size_to(to: Bib) -> StackBuilder {
 ///  - `"MOSAIC"`
 ///  - `"BRIGHTNESS"`
 ///  - `"GHOST"`
-pub fn change_looks_effect_by(effect: Bfb, by: Bib) -> StackBuilder {
+pub fn change_looks_effect_by(
+    effect: impl Into<Option<Bfb>>,
+    by: impl Into<Option<Bib>>,
+) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::looks_changeeffectby);
-        b.add_input("CHANGE", by).add_field("EFFECT", effect);
+        b.add_input_or_default("CHANGE", by)
+            .add_field_or_default("EFFECT", effect);
         b
     })
 }
@@ -373,10 +460,14 @@ pub fn change_looks_effect_by(effect: Bfb, by: Bib) -> StackBuilder {
 ///  - `"MOSAIC"`
 ///  - `"BRIGHTNESS"`
 ///  - `"GHOST"`
-pub fn set_looks_effect_to(effect: Bfb, to: Bib) -> StackBuilder {
+pub fn set_looks_effect_to(
+    effect: impl Into<Option<Bfb>>,
+    to: impl Into<Option<Bib>>,
+) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::looks_seteffectto);
-        b.add_input("TO", to).add_field("EFFECT", effect);
+        b.add_input_or_default("VALUE", to)
+            .add_field_or_default("EFFECT", effect);
         b
     })
 }
@@ -395,26 +486,34 @@ pub fn hide() -> StackBuilder {
     StackBuilder::start(BlockNormalBuilder::new(StandardOpCode::looks_hide))
 }
 
+/// Hidden block, see [`Validator::allow_hidden_blocks`](crate::validate::Validator::allow_hidden_blocks)
+pub fn hide_all_sprites() -> StackBuilder {
+    StackBuilder::start(BlockNormalBuilder::new(
+        StandardOpCode::looks_hideallsprites,
+    ))
+}
+
 /// <br/>
 /// Accepts:
 ///  - `"front"`
 ///  - `"back"`
-pub fn go_to_layer(layer: Bfb) -> StackBuilder {
+pub fn go_to_layer(layer: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::looks_gotofrontback);
-        b.add_field("FRONT_BACK", layer);
+        b.add_field_or_default("FRONT_BACK", layer);
         b
     })
 }
 
 /// <br/>
 /// `layer` Accepts:
-///  - `"foward"`
+///  - `"forward"`
 ///  - `"backward"`
-pub fn change_layer(layer: Bfb, by: Bib) -> StackBuilder {
+pub fn change_layer(layer: impl Into<Option<Bfb>>, by: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::looks_goforwardbackwardlayers);
-        b.add_input("NUM", by).add_field("FORWARD_BACKWORD", layer);
+        b.add_input_or_default("NUM", by)
+            .add_field_or_default("FORWARD_BACKWARD", layer);
         b
     })
 }
@@ -423,10 +522,10 @@ pub fn change_layer(layer: Bfb, by: Bib) -> StackBuilder {
 /// Accepts:
 /// - `"number"`
 /// - `"name"`
-pub fn costume(return_type: Bfb) -> StackBuilder {
+pub fn costume(return_type: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::looks_costumenumbername);
-        b.add_field("NUMBER_NAME", return_type);
+        b.add_field_or_default("NUMBER_NAME", return_type);
         b
     })
 }
@@ -435,10 +534,10 @@ pub fn costume(return_type: Bfb) -> StackBuilder {
 /// Accepts:
 /// - `"number"`
 /// - `"name"`
-pub fn backdrop(return_type: Bfb) -> StackBuilder {
+pub fn backdrop(return_type: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::looks_backdropnumbername);
-        b.add_field("NUMBER_NAME", return_type);
+        b.add_field_or_default("NUMBER_NAME", return_type);
         b
     })
 }
@@ -448,26 +547,26 @@ pub fn size() -> StackBuilder {
 }
 
 // Motion ======================================================================
-pub fn move_steps(steps: Bib) -> StackBuilder {
+pub fn move_steps(steps: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::motion_movesteps);
-        b.add_input("STEPS", steps);
+        b.add_input_or_default("STEPS", steps);
         b
     })
 }
 
-pub fn turn_right(degress: Bib) -> StackBuilder {
+pub fn turn_right(degress: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::motion_turnright);
-        b.add_input("DEGREES", degress);
+        b.add_input_or_default("DEGREES", degress);
         b
     })
 }
 
-pub fn turn_left(degress: Bib) -> StackBuilder {
+pub fn turn_left(degress: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::motion_turnleft);
-        b.add_input("DEGREES", degress);
+        b.add_input_or_default("DEGREES", degress);
         b
     })
 }
@@ -477,32 +576,32 @@ pub fn turn_left(degress: Bib) -> StackBuilder {
 ///  - Sprite name
 ///  - `"_mouse_"` go to mouse position
 ///  - `"_random_"` go to random position
-pub fn go_to(to: Bib) -> StackBuilder {
+pub fn go_to(to: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::motion_goto);
-        b.add_input("TO", to);
+        b.add_input_or_default("TO", to);
         b
     })
 }
 
-/// Uses as an argument to [`goto`]
+/// Uses as an argument to [`go_to`]
 ///
 /// Accepts:
 ///  - Sprite name
 ///  - `"_mouse_"` go to mouse position
 ///  - `"_random_"` go to random position
-pub fn go_to_menu(to: Bfb) -> StackBuilder {
+pub fn go_to_menu(to: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::motion_goto_menu);
-        b.add_field("TO", to).set_shadow(true);
+        b.add_field_or_default("TO", to).set_shadow(true);
         b
     })
 }
 
-pub fn goto_xy(x: Bib, y: Bib) -> StackBuilder {
+pub fn goto_xy(x: impl Into<Option<Bib>>, y: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::motion_gotoxy);
-        b.add_input("X", x).add_input("Y", y);
+        b.add_input_or_default("X", x).add_input_or_default("Y", y);
         b
     })
 }
@@ -512,10 +611,11 @@ pub fn goto_xy(x: Bib, y: Bib) -> StackBuilder {
 ///  - Sprite name
 ///  - `"_mouse_"` glide to mouse position
 ///  - `"_random_"` glide to random position
-pub fn glide_to(duration_secs: Bib, to: Bib) -> StackBuilder {
+pub fn glide_to(duration_secs: impl Into<Option<Bib>>, to: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
-        let mut b = BlockNormalBuilder::new(StandardOpCode::motion_gotoxy);
-        b.add_input("SECS", duration_secs).add_input("TO", to);
+        let mut b = BlockNormalBuilder::new(StandardOpCode::motion_glideto);
+        b.add_input_or_default("SECS", duration_secs)
+            .add_input_or_default("TO", to);
         b
     })
 }
@@ -526,26 +626,32 @@ pub fn glide_to(duration_secs: Bib, to: Bib) -> StackBuilder {
 ///  - Sprite name
 ///  - `"_mouse_"` glide to mouse position
 ///  - `"_random_"` glide to random position
-pub fn glide_to_menu(to: Bfb) -> StackBuilder {
+pub fn glide_to_menu(to: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::motion_glideto_menu);
-        b.add_field("TO", to).set_shadow(true);
+        b.add_field_or_default("TO", to).set_shadow(true);
         b
     })
 }
 
-pub fn glide_to_xy(dur: Bib, x: Bib, y: Bib) -> StackBuilder {
+pub fn glide_to_xy(
+    dur: impl Into<Option<Bib>>,
+    x: impl Into<Option<Bib>>,
+    y: impl Into<Option<Bib>>,
+) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::motion_glidesecstoxy);
-        b.add_input("SECS", dur).add_input("X", x).add_input("Y", y);
+        b.add_input_or_default("SECS", dur)
+            .add_input_or_default("X", x)
+            .add_input_or_default("Y", y);
         b
     })
 }
 
-pub fn point_in_direction(direction: Bib) -> StackBuilder {
+pub fn point_in_direction(direction: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::motion_pointindirection);
-        b.add_input("DIRECTION", direction);
+        b.add_input_or_default("DIRECTION", direction);
         b
     })
 }
@@ -554,10 +660,10 @@ pub fn point_in_direction(direction: Bib) -> StackBuilder {
 /// Accepts:
 ///  - Sprite name
 ///  - `"_mouse_"` glide to mouse position
-pub fn point_towards(towards: Bib) -> StackBuilder {
+pub fn point_towards(towards: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::motion_pointtowards);
-        b.add_input("TOWARDS", towards);
+        b.add_input_or_default("TOWARDS", towards);
         b
     })
 }
@@ -567,42 +673,42 @@ pub fn point_towards(towards: Bib) -> StackBuilder {
 /// Accepts:
 ///  - Sprite name
 ///  - `"_mouse_"` glide to mouse position
-pub fn point_towards_menu(towards: Bfb) -> StackBuilder {
+pub fn point_towards_menu(towards: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::motion_pointtowards_menu);
-        b.add_field("TOWARDS", towards).set_shadow(true);
+        b.add_field_or_default("TOWARDS", towards).set_shadow(true);
         b
     })
 }
 
-pub fn set_x(x: Bib) -> StackBuilder {
+pub fn set_x(x: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::motion_setx);
-        b.add_input("X", x);
+        b.add_input_or_default("X", x);
         b
     })
 }
 
-pub fn set_y(y: Bib) -> StackBuilder {
+pub fn set_y(y: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
-        let mut b = BlockNormalBuilder::new(StandardOpCode::motion_setx);
-        b.add_input("Y", y);
+        let mut b = BlockNormalBuilder::new(StandardOpCode::motion_sety);
+        b.add_input_or_default("Y", y);
         b
     })
 }
 
-pub fn change_x_by(by: Bib) -> StackBuilder {
+pub fn change_x_by(by: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::motion_changexby);
-        b.add_input("DX", by);
+        b.add_input_or_default("DX", by);
         b
     })
 }
 
-pub fn change_y_by(by: Bib) -> StackBuilder {
+pub fn change_y_by(by: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::motion_changeyby);
-        b.add_input("DY", by);
+        b.add_input_or_default("DY", by);
         b
     })
 }
@@ -618,10 +724,10 @@ pub fn if_on_edge_bounce() -> StackBuilder {
 ///  - `"left-right"`
 ///  - `"don't rotate"`
 ///  - `"all around"`
-pub fn set_rotation_style(style: Bfb) -> StackBuilder {
+pub fn set_rotation_style(style: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::motion_setrotationstyle);
-        b.add_field("STYLE", style);
+        b.add_field_or_default("STYLE", style);
         b
     })
 }
@@ -639,66 +745,74 @@ pub fn direction() -> StackBuilder {
 }
 
 // Operators ===================================================================
-pub fn add(lhs: Bib, rhs: Bib) -> StackBuilder {
+pub fn add(lhs: impl Into<Option<Bib>>, rhs: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::operator_add);
-        b.add_input("NUM1", lhs).add_input("NUM2", rhs);
+        b.add_input_or_default("NUM1", lhs)
+            .add_input_or_default("NUM2", rhs);
         b
     })
 }
 
-pub fn sub(lhs: Bib, rhs: Bib) -> StackBuilder {
+pub fn sub(lhs: impl Into<Option<Bib>>, rhs: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::operator_subtract);
-        b.add_input("NUM1", lhs).add_input("NUM2", rhs);
+        b.add_input_or_default("NUM1", lhs)
+            .add_input_or_default("NUM2", rhs);
         b
     })
 }
 
-pub fn mul(lhs: Bib, rhs: Bib) -> StackBuilder {
+pub fn mul(lhs: impl Into<Option<Bib>>, rhs: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::operator_multiply);
-        b.add_input("NUM1", lhs).add_input("NUM2", rhs);
+        b.add_input_or_default("NUM1", lhs)
+            .add_input_or_default("NUM2", rhs);
         b
     })
 }
 
-pub fn div(lhs: Bib, rhs: Bib) -> StackBuilder {
+pub fn div(lhs: impl Into<Option<Bib>>, rhs: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::operator_divide);
-        b.add_input("NUM1", lhs).add_input("NUM2", rhs);
+        b.add_input_or_default("NUM1", lhs)
+            .add_input_or_default("NUM2", rhs);
         b
     })
 }
 
-pub fn random(from: Bib, to: Bib) -> StackBuilder {
+pub fn random(from: impl Into<Option<Bib>>, to: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::operator_random);
-        b.add_input("FROM", from).add_input("TO", to);
+        b.add_input_or_default("FROM", from)
+            .add_input_or_default("TO", to);
         b
     })
 }
 
-pub fn less_than(lhs: Bib, rhs: Bib) -> StackBuilder {
+pub fn less_than(lhs: impl Into<Option<Bib>>, rhs: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::operator_lt);
-        b.add_input("OPERAND1", lhs).add_input("OPERAND2", rhs);
+        b.add_input_or_default("OPERAND1", lhs)
+            .add_input_or_default("OPERAND2", rhs);
         b
     })
 }
 
-pub fn greater_than(lhs: Bib, rhs: Bib) -> StackBuilder {
+pub fn greater_than(lhs: impl Into<Option<Bib>>, rhs: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::operator_gt);
-        b.add_input("OPERAND1", lhs).add_input("OPERAND2", rhs);
+        b.add_input_or_default("OPERAND1", lhs)
+            .add_input_or_default("OPERAND2", rhs);
         b
     })
 }
 
-pub fn equals(lhs: Bib, rhs: Bib) -> StackBuilder {
+pub fn equals(lhs: impl Into<Option<Bib>>, rhs: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::operator_equals);
-        b.add_input("OPERAND1", lhs).add_input("OPERAND2", rhs);
+        b.add_input_or_default("OPERAND1", lhs)
+            .add_input_or_default("OPERAND2", rhs);
         b
     })
 }
@@ -727,50 +841,54 @@ pub fn not(val: Bib) -> StackBuilder {
     })
 }
 
-pub fn join(a: Bib, b: Bib) -> StackBuilder {
+pub fn join(a: impl Into<Option<Bib>>, b: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut bl = BlockNormalBuilder::new(StandardOpCode::operator_join);
-        bl.add_input("STRING1", a).add_input("STRING2", b);
+        bl.add_input_or_default("STRING1", a)
+            .add_input_or_default("STRING2", b);
         bl
     })
 }
 
-pub fn letter_of(idx: Bib, text: Bib) -> StackBuilder {
+pub fn letter_of(idx: impl Into<Option<Bib>>, text: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::operator_letter_of);
-        b.add_input("LETTER", idx).add_input("STRING", text);
+        b.add_input_or_default("LETTER", idx)
+            .add_input_or_default("STRING", text);
         b
     })
 }
 
-pub fn length_of(text: Bib) -> StackBuilder {
+pub fn length_of(text: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::operator_length);
-        b.add_input("STRING", text);
+        b.add_input_or_default("STRING", text);
         b
     })
 }
 
-pub fn contains(text: Bib, contains: Bib) -> StackBuilder {
+pub fn contains(text: impl Into<Option<Bib>>, contains: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::operator_contains);
-        b.add_input("STRING1", text).add_input("STRING2", contains);
+        b.add_input_or_default("STRING1", text)
+            .add_input_or_default("STRING2", contains);
         b
     })
 }
 
-pub fn modulo(dividend: Bib, divisor: Bib) -> StackBuilder {
+pub fn modulo(dividend: impl Into<Option<Bib>>, divisor: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::operator_mod);
-        b.add_input("NUM1", dividend).add_input("NUM2", divisor);
+        b.add_input_or_default("NUM1", dividend)
+            .add_input_or_default("NUM2", divisor);
         b
     })
 }
 
-pub fn round(val: Bib) -> StackBuilder {
+pub fn round(val: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::operator_round);
-        b.add_input("NUM", val);
+        b.add_input_or_default("NUM", val);
         b
     })
 }
@@ -791,10 +909,11 @@ pub fn round(val: Bib) -> StackBuilder {
 ///  - `"log"`
 ///  - `"e ^"`
 ///  - `"10 ^"`
-pub fn math_op(op: Bfb, val: Bib) -> StackBuilder {
+pub fn math_op(op: impl Into<Option<Bfb>>, val: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::operator_mathop);
-        b.add_input("NUM", val).add_field("OPERATOR", op);
+        b.add_input_or_default("NUM", val)
+            .add_field_or_default("OPERATOR", op);
         b
     })
 }
@@ -806,10 +925,10 @@ pub fn math_op(op: Bfb, val: Bib) -> StackBuilder {
 ///  - Sprite name
 ///  - `"_mouse_"`
 ///  - `"_edge_"`
-pub fn touching(what: Bib) -> StackBuilder {
+pub fn touching(what: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::sensing_touchingobject);
-        b.add_input("TOUCHINGOBJECTMENU", what);
+        b.add_input_or_default("TOUCHINGOBJECTMENU", what);
         b
     })
 }
@@ -820,26 +939,31 @@ pub fn touching(what: Bib) -> StackBuilder {
 ///  - Sprite name
 ///  - `"_mouse_"`
 ///  - `"_edge_"`
-pub fn touching_menu(what: Bfb) -> StackBuilder {
+pub fn touching_menu(what: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::sensing_touchingobjectmenu);
-        b.add_field("TOUCHINGOBJECTMENU", what).set_shadow(true);
+        b.add_field_or_default("TOUCHINGOBJECTMENU", what)
+            .set_shadow(true);
         b
     })
 }
 
-pub fn touching_color(color: Bib) -> StackBuilder {
+pub fn touching_color(color: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::sensing_touchingcolor);
-        b.add_input("COLOR", color);
+        b.add_input_or_default("COLOR", color);
         b
     })
 }
 
-pub fn color_touching_color(color_a: Bib, color_b: Bib) -> StackBuilder {
+pub fn color_touching_color(
+    color_a: impl Into<Option<Bib>>,
+    color_b: impl Into<Option<Bib>>,
+) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::sensing_coloristouchingcolor);
-        b.add_input("COLOR", color_a).add_input("COLOR2", color_b);
+        b.add_input_or_default("COLOR", color_a)
+            .add_input_or_default("COLOR2", color_b);
         b
     })
 }
@@ -848,10 +972,10 @@ pub fn color_touching_color(color_a: Bib, color_b: Bib) -> StackBuilder {
 /// Accepts:
 ///  - Sprite name
 ///  - `"_mouse_"`
-pub fn distance_to(what: Bib) -> StackBuilder {
+pub fn distance_to(what: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
-        let mut b = BlockNormalBuilder::new(StandardOpCode::sensing_coloristouchingcolor);
-        b.add_input("DISTANCETOMENU", what);
+        let mut b = BlockNormalBuilder::new(StandardOpCode::sensing_distanceto);
+        b.add_input_or_default("DISTANCETOMENU", what);
         b
     })
 }
@@ -861,18 +985,19 @@ pub fn distance_to(what: Bib) -> StackBuilder {
 /// Accepts:
 ///  - Sprite name
 ///  - `"_mouse_"`
-pub fn distance_to_menu(what: Bfb) -> StackBuilder {
+pub fn distance_to_menu(what: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
-        let mut b = BlockNormalBuilder::new(StandardOpCode::sensing_coloristouchingcolor);
-        b.add_field("DISTANCETOMENU", what).set_shadow(true);
+        let mut b = BlockNormalBuilder::new(StandardOpCode::sensing_distancetomenu);
+        b.add_field_or_default("DISTANCETOMENU", what)
+            .set_shadow(true);
         b
     })
 }
 
-pub fn ask_and_wait(prompt_message: Bib) -> StackBuilder {
+pub fn ask_and_wait(prompt_message: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::sensing_askandwait);
-        b.add_input("QUESTION", prompt_message);
+        b.add_input_or_default("QUESTION", prompt_message);
         b
     })
 }
@@ -891,10 +1016,10 @@ pub fn answer() -> StackBuilder {
 ///  - `"down arrow"`
 ///  - Number 0 - 9
 ///  - Letter a - z
-pub fn key_pressed(key: Bib) -> StackBuilder {
+pub fn key_pressed(key: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::sensing_keypressed);
-        b.add_input("KEY_OPTION", key);
+        b.add_input_or_default("KEY_OPTION", key);
         b
     })
 }
@@ -910,10 +1035,10 @@ pub fn key_pressed(key: Bib) -> StackBuilder {
 ///  - `"down arrow"`
 ///  - Number 0 - 9
 ///  - Letter a - z
-pub fn key_menu(key: Bib) -> StackBuilder {
+pub fn key_menu(key: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::sensing_keyoptions);
-        b.add_input("KEY_OPTION", key).set_shadow(true);
+        b.add_field_or_default("KEY_OPTION", key).set_shadow(true);
         b
     })
 }
@@ -934,10 +1059,10 @@ pub fn mouse_y() -> StackBuilder {
 /// Accepts:
 ///  - `"not draggable"`
 ///  - `"draggable"`
-pub fn set_drag_mode(mode: Bfb) -> StackBuilder {
+pub fn set_drag_mode(mode: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::sensing_setdragmode);
-        b.add_field("DRAG_MODE", mode);
+        b.add_field_or_default("DRAG_MODE", mode);
         b
     })
 }
@@ -946,6 +1071,13 @@ pub fn loudness() -> StackBuilder {
     StackBuilder::start(BlockNormalBuilder::new(StandardOpCode::sensing_loudness))
 }
 
+/// Hidden block, see [`Validator::allow_hidden_blocks`](crate::validate::Validator::allow_hidden_blocks)
+///
+/// True when loudness is greater than 10.
+pub fn loud() -> StackBuilder {
+    StackBuilder::start(BlockNormalBuilder::new(StandardOpCode::sensing_loud))
+}
+
 pub fn timer() -> StackBuilder {
     StackBuilder::start(BlockNormalBuilder::new(StandardOpCode::sensing_timer))
 }
@@ -978,10 +1110,11 @@ pub fn reset_timer() -> StackBuilder {
 ///      - `"costume name"`
 ///      - `"size"`
 ///      - `"volume"`
-pub fn var_of(var: Bfb, what: Bib) -> StackBuilder {
+pub fn var_of(var: Bfb, what: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::sensing_of);
-        b.add_input("OBJECT", what).add_field("PROPERTY", var);
+        b.add_input_or_default("OBJECT", what)
+            .add_field("PROPERTY", var);
         b
     })
 }
@@ -991,10 +1124,10 @@ pub fn var_of(var: Bfb, what: Bib) -> StackBuilder {
 /// `what` Accepts:
 ///   - Sprite name
 ///   - `"_stage_"`
-pub fn var_of_object_menu(what: Bfb) -> StackBuilder {
+pub fn var_of_object_menu(what: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::sensing_of_object_menu);
-        b.add_field("OBJECT", what).set_shadow(true);
+        b.add_field_or_default("OBJECT", what).set_shadow(true);
         b
     })
 }
@@ -1008,10 +1141,10 @@ pub fn var_of_object_menu(what: Bfb) -> StackBuilder {
 ///  - `"DATE"`
 ///  - `"MONTH"`
 ///  - `"YEAR"`
-pub fn current_datetime(format: Bfb) -> StackBuilder {
+pub fn current_datetime(format: impl Into<Option<Bfb>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::sensing_current);
-        b.add_field("CURRENTMENU", format);
+        b.add_field_or_default("CURRENTMENU", format);
         b
     })
 }
@@ -1026,15 +1159,22 @@ pub fn username() -> StackBuilder {
     StackBuilder::start(BlockNormalBuilder::new(StandardOpCode::sensing_username))
 }
 
+/// Hidden block, see [`Validator::allow_hidden_blocks`](crate::validate::Validator::allow_hidden_blocks)
+///
+/// Always report nothing in Scratch 3.
+pub fn user_id() -> StackBuilder {
+    StackBuilder::start(BlockNormalBuilder::new(StandardOpCode::sensing_userid))
+}
+
 // Sound =======================================================================
 
 /// <br/>
 /// Accepts:
 ///  - Sound name
-pub fn play_sound_until_done(sound: Bib) -> StackBuilder {
+pub fn play_sound_until_done(sound: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::sound_playuntildone);
-        b.add_input("SOUND_MENU", sound);
+        b.add_input_or_default("SOUND_MENU", sound);
         b
     })
 }
@@ -1042,10 +1182,10 @@ pub fn play_sound_until_done(sound: Bib) -> StackBuilder {
 /// <br/>
 /// Accepts:
 ///  - Sound name
-pub fn play_sound(sound: Bib) -> StackBuilder {
+pub fn play_sound(sound: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::sound_play);
-        b.add_input("SOUND_MENU", sound);
+        b.add_input_or_default("SOUND_MENU", sound);
         b
     })
 }
@@ -1070,10 +1210,14 @@ pub fn stop_all_sound() -> StackBuilder {
 /// Accepts:
 ///  - `"PITCH"`
 ///  - `"PAN"`
-pub fn change_sound_effect_by(effect: Bfb, by: Bib) -> StackBuilder {
+pub fn change_sound_effect_by(
+    effect: impl Into<Option<Bfb>>,
+    by: impl Into<Option<Bib>>,
+) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::sound_changeeffectby);
-        b.add_input("VALUE", by).add_field("EFFECT", effect);
+        b.add_input_or_default("VALUE", by)
+            .add_field_or_default("EFFECT", effect);
         b
     })
 }
@@ -1082,10 +1226,14 @@ pub fn change_sound_effect_by(effect: Bfb, by: Bib) -> StackBuilder {
 /// Accepts:
 ///  - `"PITCH"`
 ///  - `"PAN"`
-pub fn set_sound_effect_to(effect: Bfb, to: Bib) -> StackBuilder {
+pub fn set_sound_effect_to(
+    effect: impl Into<Option<Bfb>>,
+    to: impl Into<Option<Bib>>,
+) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::sound_seteffectto);
-        b.add_input("VALUE", to).add_field("EFFECT", effect);
+        b.add_input_or_default("VALUE", to)
+            .add_field_or_default("EFFECT", effect);
         b
     })
 }
@@ -1094,18 +1242,18 @@ pub fn clear_sound_effects() -> StackBuilder {
     StackBuilder::start(BlockNormalBuilder::new(StandardOpCode::sound_cleareffects))
 }
 
-pub fn set_volume_to(volume: Bib) -> StackBuilder {
+pub fn set_volume_to(volume: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::sound_setvolumeto);
-        b.add_input("VOLUME", volume);
+        b.add_input_or_default("VOLUME", volume);
         b
     })
 }
 
-pub fn change_volume_by(by: Bib) -> StackBuilder {
+pub fn change_volume_by(by: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
-        let mut b = BlockNormalBuilder::new(StandardOpCode::sound_changeeffectby);
-        b.add_input("VOLUME", by);
+        let mut b = BlockNormalBuilder::new(StandardOpCode::sound_changevolumeby);
+        b.add_input_or_default("VOLUME", by);
         b
     })
 }
@@ -1131,18 +1279,29 @@ pub fn global_list<S: Into<String>>(name: S) -> StackBuilder {
     StackBuilder::start_varlist(BlockVarListBuilder::global_list(name))
 }
 
-pub fn set_var_to(var: Bfb, to: Bib) -> StackBuilder {
+/// The list reporter as a normal block instead of the compact one from [`sprite_list`] and [`global_list`].
+pub fn list_contents(list: Bfb) -> StackBuilder {
+    StackBuilder::start({
+        let mut b = BlockNormalBuilder::new(StandardOpCode::data_listcontents);
+        b.add_field("LIST", list);
+        b
+    })
+}
+
+pub fn set_var_to(var: Bfb, to: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::data_setvariableto);
-        b.add_input("VALUE", to).add_field("VARIABLE", var);
+        b.add_input_or_default("VALUE", to)
+            .add_field("VARIABLE", var);
         b
     })
 }
 
-pub fn change_var_by(var: Bfb, by: Bib) -> StackBuilder {
+pub fn change_var_by(var: Bfb, by: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::data_changevariableby);
-        b.add_input("VALUE", by).add_field("VARIABLE", var);
+        b.add_input_or_default("VALUE", by)
+            .add_field("VARIABLE", var);
         b
     })
 }
@@ -1163,18 +1322,18 @@ pub fn hide_var(var: Bfb) -> StackBuilder {
     })
 }
 
-pub fn add_to_list(list: Bfb, item: Bib) -> StackBuilder {
+pub fn add_to_list(list: Bfb, item: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::data_addtolist);
-        b.add_input("ITEM", item).add_field("LIST", list);
+        b.add_input_or_default("ITEM", item).add_field("LIST", list);
         b
     })
 }
 
-pub fn delete_in_list(list: Bfb, idx: Bib) -> StackBuilder {
+pub fn delete_in_list(list: Bfb, idx: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::data_deleteoflist);
-        b.add_input("INDEX", idx).add_field("LIST", list);
+        b.add_input_or_default("INDEX", idx).add_field("LIST", list);
         b
     })
 }
@@ -1187,38 +1346,46 @@ pub fn delete_all_in_list(list: Bfb) -> StackBuilder {
     })
 }
 
-pub fn insert_in_list(list: Bfb, idx: Bib, item: Bib) -> StackBuilder {
+pub fn insert_in_list(
+    list: Bfb,
+    idx: impl Into<Option<Bib>>,
+    item: impl Into<Option<Bib>>,
+) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::data_insertatlist);
-        b.add_input("INDEX", idx)
-            .add_input("ITEM", item)
+        b.add_input_or_default("INDEX", idx)
+            .add_input_or_default("ITEM", item)
             .add_field("LIST", list);
         b
     })
 }
 
-pub fn replace_in_list(list: Bfb, idx: Bib, item: Bib) -> StackBuilder {
+pub fn replace_in_list(
+    list: Bfb,
+    idx: impl Into<Option<Bib>>,
+    item: impl Into<Option<Bib>>,
+) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::data_replaceitemoflist);
-        b.add_input("INDEX", idx)
-            .add_input("ITEM", item)
+        b.add_input_or_default("INDEX", idx)
+            .add_input_or_default("ITEM", item)
             .add_field("LIST", list);
         b
     })
 }
 
-pub fn item_in_list(list: Bfb, idx: Bib) -> StackBuilder {
+pub fn item_in_list(list: Bfb, idx: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::data_itemoflist);
-        b.add_input("INDEX", idx).add_field("LIST", list);
+        b.add_input_or_default("INDEX", idx).add_field("LIST", list);
         b
     })
 }
 
-pub fn count_of_item_in_list(list: Bfb, item: Bib) -> StackBuilder {
+pub fn count_of_item_in_list(list: Bfb, item: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::data_itemnumoflist);
-        b.add_input("ITEM", item).add_field("LIST", list);
+        b.add_input_or_default("ITEM", item).add_field("LIST", list);
         b
     })
 }
@@ -1231,10 +1398,10 @@ pub fn length_of_list(list: Bfb) -> StackBuilder {
     })
 }
 
-pub fn list_contains(list: Bfb, item: Bib) -> StackBuilder {
+pub fn list_contains(list: Bfb, item: impl Into<Option<Bib>>) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new(StandardOpCode::data_listcontainsitem);
-        b.add_input("ITEM", item).add_field("LIST", list);
+        b.add_input_or_default("ITEM", item).add_field("LIST", list);
         b
     })
 }
@@ -1256,20 +1423,23 @@ pub fn hide_list(list: Bfb) -> StackBuilder {
 }
 
 // My Blocks ========================================================================
-pub fn define_custom_block<S: Into<String>>(name: S) -> StackBuilder {
-    let custom_block = CustomBlockBuilder::new(name);
+/// `proccode` is the one from [`CustomBlockTy::proccode`](crate::custom_block::CustomBlockTy::proccode)
+pub fn define_custom_block<S: Into<String>>(proccode: S) -> StackBuilder {
+    let custom_block = CustomBlockBuilder::new(proccode);
 
     StackBuilder {
         stack: vec![BlockBuilder::CustomBlock(custom_block)],
     }
 }
 
+/// `proccode` is the one from [`CustomBlockTy::proccode`](crate::custom_block::CustomBlockTy::proccode)
+/// and `args` are keyed by argument name.
 pub fn call_custom_block<S: Into<String>, T: Into<String>>(
-    name: S,
+    proccode: S,
     args: HashMap<T, Bib>,
 ) -> StackBuilder {
     let mut custom_func_call = CustomFuncCallBuilder::new();
-    custom_func_call.set_name(name.into());
+    custom_func_call.set_proccode(proccode.into());
     for (k, v) in args {
         custom_func_call.add_input(k.into(), v);
     }
@@ -1302,10 +1472,10 @@ pub fn custom_block_var_string_number<S: Into<String>>(name: S) -> StackBuilder
 }
 
 // Translate ========================================================================
-pub fn translate_to<S: Into<String>>(string: Bib, lang: S) -> StackBuilder {
+pub fn translate_to<S: Into<String>>(string: impl Into<Option<Bib>>, lang: S) -> StackBuilder {
     StackBuilder::start({
         let mut b = BlockNormalBuilder::new("translate_getTranslate");
-        b.add_input("WORDS", string);
+        b.add_input_or_default("WORDS", string);
         b.add_input(
             "LANGUAGE",
             BlockInputBuilder::shadow_stack(StackBuilder::start({
@@ -0,0 +1,190 @@
+//! Loops and branches that Scratch doesn't have, made from the blocks it has
+//!
+//! Everything here returns an ordinary [`StackBuilder`].
+//! Variables that these need, like the index of [`for_each_item`] or the flag of [`loop_with_break`],
+//! are passed in and have to be added to the target like any other variable.
+
+use sb_sbity::block::BlockInputValue;
+
+use crate::{
+    block::{BlockFieldBuilder, BlockInputBuilder, FieldKind},
+    blocks::{
+        change_var_by, equals, global_var, greater_than, if_, if_else, item_in_list,
+        length_of_list, less_than, mul, not, or, repeat, repeat_until, set_var_to, sprite_var, sub,
+    },
+    stack::StackBuilder,
+};
+
+type Bfb = BlockFieldBuilder;
+type Bib = BlockInputBuilder;
+
+/// [`match_value`] with at least this many cases that are all numbers uses a binary search
+const BINARY_SEARCH_CASES: usize = 8;
+
+/// Cases at the bottom of the binary search are checked one by one
+const BINARY_SEARCH_LEAF: usize = 3;
+
+/// Set `var` to `from`, `from + step`, ... while it hasn't gone past `to` and run `body` each time.
+///
+/// `to` and `step` are evaluated every time around the loop and `step` can be negative.
+/// Nothing is run when `step` is 0.
+pub fn for_range(var: Bfb, from: Bib, to: Bib, step: Bib, body: StackBuilder) -> StackBuilder {
+    let past_end = greater_than(
+        Bib::stack(mul(Bib::stack(sub(reporter(&var), to)), step.clone())),
+        number(0.),
+    );
+    let is_stuck = equals(step.clone(), number(0.));
+    set_var_to(var.clone(), from).next(repeat_until(
+        Bib::stack(or(Bib::stack(past_end), Bib::stack(is_stuck))),
+        substack(body.next(change_var_by(var, step))),
+    ))
+}
+
+/// Set `var` to every item of `list` from the first to the last and run `body` each time.
+///
+/// The length of the list is only checked once before the loop.
+/// The position of the item is kept in `index`.
+pub fn for_each_item(list: Bfb, var: Bfb, index: Bfb, body: StackBuilder) -> StackBuilder {
+    set_var_to(index.clone(), number(0.)).next(repeat(
+        Bib::stack(length_of_list(list.clone())),
+        substack(
+            change_var_by(index.clone(), number(1.))
+                .next(set_var_to(
+                    var,
+                    Bib::stack(item_in_list(list, reporter(&index))),
+                ))
+                .next(body),
+        ),
+    ))
+}
+
+/// Run `body` as long as `condition` is true.
+///
+/// Unlike [`blocks::while_`](crate::blocks::while_) this is made from `repeat until` which is in the editor's palette.
+pub fn repeat_while(condition: Bib, body: StackBuilder) -> StackBuilder {
+    repeat_until(Bib::stack(not(condition)), substack(body))
+}
+
+/// Run `body` as long as `condition` is true, or forever when it's [`None`], until [`break_`] is run with the same `flag`.
+///
+/// `flag` is set to 0 before the loop and to 1 by [`break_`].
+///
+/// The loop only stops at the end of the current time around,
+/// so anything after [`break_`] in `body` still runs. Put it last in its branch.
+pub fn loop_with_break(flag: Bfb, condition: Option<Bib>, body: StackBuilder) -> StackBuilder {
+    let broken = equals(reporter(&flag), number(1.));
+    let stop = match condition {
+        Some(condition) => or(Bib::stack(broken), Bib::stack(not(condition))),
+        None => broken,
+    };
+    set_var_to(flag, number(0.)).next(repeat_until(Bib::stack(stop), substack(body)))
+}
+
+/// Stop the [`loop_with_break`] that has this `flag`.
+pub fn break_(flag: Bfb) -> StackBuilder {
+    set_var_to(flag, number(1.))
+}
+
+/// Run the body of the first case that is equal to `value`, or `default` if there isn't one.
+///
+/// Cases are checked with `=` one after the other in nested if-else.
+/// When there are many cases and they are all numbers they're checked with a binary search instead.
+/// `value` is evaluated for every check so use a variable when it's something like `pick random`.
+///
+/// [`None`] when there are no cases and no default since there's nothing to run.
+pub fn match_value(
+    value: Bib,
+    cases: Vec<(String, StackBuilder)>,
+    default: Option<StackBuilder>,
+) -> Option<StackBuilder> {
+    let numbers: Option<Vec<f64>> = cases.iter().map(|(key, _)| key.parse().ok()).collect();
+    match numbers {
+        Some(numbers) if cases.len() >= BINARY_SEARCH_CASES => {
+            let mut cases: Vec<(f64, StackBuilder)> = numbers
+                .into_iter()
+                .zip(cases.into_iter().map(|(_, body)| body))
+                .collect();
+            // The first case wins like it does in the if-else chain
+            cases.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+            cases.dedup_by(|(a, _), (b, _)| a == b);
+            Some(binary_search(&value, &cases, &default))
+        }
+        _ => chain(
+            &value,
+            cases
+                .into_iter()
+                .map(|(key, body)| (text(key), body))
+                .collect(),
+            default,
+        ),
+    }
+}
+
+fn binary_search(
+    value: &Bib,
+    cases: &[(f64, StackBuilder)],
+    default: &Option<StackBuilder>,
+) -> StackBuilder {
+    if cases.len() <= BINARY_SEARCH_LEAF {
+        // Leaves always have cases so there's a stack
+        return chain(
+            value,
+            cases
+                .iter()
+                .map(|(key, body)| (number(*key), body.clone()))
+                .collect(),
+            default.clone(),
+        )
+        .unwrap_or_default();
+    }
+    let (lower, upper) = cases.split_at(cases.len() / 2);
+    if_else(
+        Bib::stack(less_than(value.clone(), number(upper[0].0))),
+        substack(binary_search(value, lower, default)),
+        substack(binary_search(value, upper, default)),
+    )
+}
+
+fn chain(
+    value: &Bib,
+    cases: Vec<(Bib, StackBuilder)>,
+    default: Option<StackBuilder>,
+) -> Option<StackBuilder> {
+    let mut otherwise = default;
+    for (key, body) in cases.into_iter().rev() {
+        let condition = Bib::stack(equals(value.clone(), key));
+        otherwise = Some(match otherwise {
+            Some(otherwise) => if_else(condition, substack(body), substack(otherwise)),
+            None => if_(condition, substack(body)),
+        });
+    }
+    otherwise
+}
+
+/// Nothing in the C-block when `body` is empty
+fn substack(body: StackBuilder) -> Option<Bib> {
+    if body.stack.is_empty() {
+        None
+    } else {
+        Some(Bib::stack(body))
+    }
+}
+
+fn reporter(var: &Bfb) -> Bib {
+    Bib::stack(match var.kind {
+        FieldKind::GlobalVariable => global_var(var.value.clone()),
+        _ => sprite_var(var.value.clone()),
+    })
+}
+
+fn number(value: f64) -> Bib {
+    Bib::value(BlockInputValue::Number {
+        value: value.into(),
+    })
+}
+
+fn text(value: String) -> Bib {
+    Bib::value(BlockInputValue::String {
+        value: value.into(),
+    })
+}
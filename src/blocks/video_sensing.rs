@@ -0,0 +1,111 @@
+//! Video sensing extension blocks
+//!
+//! The camera is only turned on when the project loads with
+//! [`StageBuilder::video_state`](crate::target::StageBuilder::video_state) set to something other than
+//! [`VideoState::Off`](sb_sbity::target::VideoState::Off) or when [`video_toggle`] is run.
+//! [`StageBuilder::video_transparency`](crate::target::StageBuilder::video_transparency) is the starting transparency.
+//!
+//! Blocks that ended with menu is a visual menu in scratch, just like in [`crate::blocks`].
+
+use crate::{
+    block::{BlockFieldBuilder, BlockInputBuilder, BlockNormalBuilder},
+    stack::StackBuilder,
+};
+
+type Bfb = BlockFieldBuilder;
+type Bib = BlockInputBuilder;
+
+/// <br/>
+/// `reference` is the amount of motion from 1 to 100
+pub fn when_motion_greater_than(reference: impl Into<Option<Bib>>) -> StackBuilder {
+    StackBuilder::start({
+        let mut b = BlockNormalBuilder::new("videoSensing_whenMotionGreaterThan");
+        b.add_input_or_default("REFERENCE", reference);
+        b
+    })
+}
+
+/// <br/>
+/// `attribute` Accepts:
+///  - `"motion"`
+///  - `"direction"`
+///
+/// `subject` Accepts:
+///  - `"this sprite"`
+///  - `"Stage"`
+pub fn video_on(
+    attribute: impl Into<Option<Bib>>,
+    subject: impl Into<Option<Bib>>,
+) -> StackBuilder {
+    StackBuilder::start({
+        let mut b = BlockNormalBuilder::new("videoSensing_videoOn");
+        b.add_input_or_default("ATTRIBUTE", attribute)
+            .add_input_or_default("SUBJECT", subject);
+        b
+    })
+}
+
+/// Uses as an argument to [`video_on`] in `attribute`
+///
+/// Accepts:
+///  - `"motion"`
+///  - `"direction"`
+pub fn video_attribute_menu(attribute: impl Into<Option<Bfb>>) -> StackBuilder {
+    StackBuilder::start({
+        let mut b = BlockNormalBuilder::new("videoSensing_menu_ATTRIBUTE");
+        b.add_field_or_default("ATTRIBUTE", attribute)
+            .set_shadow(true);
+        b
+    })
+}
+
+/// Uses as an argument to [`video_on`] in `subject`
+///
+/// Accepts:
+///  - `"this sprite"`
+///  - `"Stage"`
+pub fn video_subject_menu(subject: impl Into<Option<Bfb>>) -> StackBuilder {
+    StackBuilder::start({
+        let mut b = BlockNormalBuilder::new("videoSensing_menu_SUBJECT");
+        b.add_field_or_default("SUBJECT", subject).set_shadow(true);
+        b
+    })
+}
+
+/// <br/>
+/// Accepts:
+///  - `"off"`
+///  - `"on"`
+///  - `"on-flipped"`
+pub fn video_toggle(video_state: impl Into<Option<Bib>>) -> StackBuilder {
+    StackBuilder::start({
+        let mut b = BlockNormalBuilder::new("videoSensing_videoToggle");
+        b.add_input_or_default("VIDEO_STATE", video_state);
+        b
+    })
+}
+
+/// Uses as an argument to [`video_toggle`]
+///
+/// Accepts:
+///  - `"off"`
+///  - `"on"`
+///  - `"on-flipped"`
+pub fn video_state_menu(video_state: impl Into<Option<Bfb>>) -> StackBuilder {
+    StackBuilder::start({
+        let mut b = BlockNormalBuilder::new("videoSensing_menu_VIDEO_STATE");
+        b.add_field_or_default("VIDEO_STATE", video_state)
+            .set_shadow(true);
+        b
+    })
+}
+
+/// <br/>
+/// `transparency` is from 0 to 100
+pub fn set_video_transparency(transparency: impl Into<Option<Bib>>) -> StackBuilder {
+    StackBuilder::start({
+        let mut b = BlockNormalBuilder::new("videoSensing_setVideoTransparency");
+        b.add_input_or_default("TRANSPARENCY", transparency);
+        b
+    })
+}
@@ -15,3 +15,28 @@ pub struct TargetContext<'a> {
     pub all_broadcasts: &'a HashMap<String, Uid>,
     pub custom_blocks: &'a Vec<CustomBlockTy>,
 }
+
+impl TargetContext<'_> {
+    /// Variable of this sprite, or the global one with the same name
+    pub fn sprite_var(&self, name: &str) -> Option<&Uid> {
+        self.this_sprite_vars
+            .get(name)
+            .or_else(|| self.global_vars.get(name))
+    }
+
+    /// List of this sprite, or the global one with the same name
+    pub fn sprite_list(&self, name: &str) -> Option<&Uid> {
+        self.this_sprite_lists
+            .get(name)
+            .or_else(|| self.global_lists.get(name))
+    }
+
+    /// Custom block of this target, made up from `proccode` if the target doesn't have it
+    pub fn custom_block(&self, proccode: &str) -> CustomBlockTy {
+        self.custom_blocks
+            .iter()
+            .find(|ty| ty.proccode() == proccode)
+            .cloned()
+            .unwrap_or_else(|| CustomBlockTy::from_proccode(proccode))
+    }
+}
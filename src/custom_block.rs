@@ -3,30 +3,37 @@ use std::collections::HashMap;
 use sb_sbity::{
     block::{Block, BlockInputValue, BlockMutation, BlockMutationEnum, BlockNormal},
     comment::Comment,
-    value::ValueWithBool,
+    value::{Value, ValueWithBool},
 };
 
 use crate::{
+    block::{BlockBuilder, StackOrValue},
+    blocks::{
+        add_to_list, custom_block_var_boolean, custom_block_var_string_number, delete_in_list,
+        item_in_list, length_of_list, replace_in_list, set_var_to, stop, sub,
+    },
     build_context::TargetContext,
+    opcode::OpCode,
     prelude::{
-        BlockFieldBuilder, BlockInputBuilder, BlockNormalBuilder, CommentBuilder, StandardOpCode,
-        Uid,
+        BlockFieldBuilder, BlockInputBuilder, BlockNormalBuilder, CommentBuilder, FieldKind,
+        StandardOpCode, Uid,
     },
     stack::StackBuilder,
 };
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct CustomBlockBuilder {
-    name: String,
+    proccode: String,
     x: Option<f64>,
     y: Option<f64>,
     comment: Option<CommentBuilder>,
 }
 
 impl CustomBlockBuilder {
-    pub fn new<S: Into<String>>(name: S) -> CustomBlockBuilder {
+    /// `proccode` is the one from [`CustomBlockTy::proccode`]
+    pub fn new<S: Into<String>>(proccode: S) -> CustomBlockBuilder {
         CustomBlockBuilder {
-            name: name.into(),
+            proccode: proccode.into(),
             ..Default::default()
         }
     }
@@ -52,6 +59,18 @@ impl CustomBlockBuilder {
         self
     }
 
+    pub fn pos(&self) -> (Option<f64>, Option<f64>) {
+        (self.x, self.y)
+    }
+
+    pub fn proccode(&self) -> &str {
+        &self.proccode
+    }
+
+    pub fn comment_mut(&mut self) -> Option<&mut CommentBuilder> {
+        self.comment.as_mut()
+    }
+
     pub fn build(
         self,
         my_uid: &Uid,
@@ -61,17 +80,12 @@ impl CustomBlockBuilder {
     ) -> BlockNormal {
         let CustomBlockBuilder {
             comment,
-            name,
+            proccode,
             x,
             y,
         } = self;
 
-        let ty = target_context
-            .custom_blocks
-            .iter()
-            .find(|ty| ty.name() == name)
-            .expect("CustomBlock not found")
-            .clone();
+        let ty = target_context.custom_block(&proccode);
 
         // let (params_id, custom_func_ty) =
         //     generate_func_input_block(my_uid.clone(), &args, final_stack, warp);
@@ -83,19 +97,16 @@ impl CustomBlockBuilder {
                 BlockInputBuilder::shadow_stack(StackBuilder::start({
                     let mut b = BlockNormalBuilder::new(match &ty {
                         CustomBlockInputType::Text(_) => unreachable!(),
-                        CustomBlockInputType::StringOrNumber(_) => {
+                        CustomBlockInputType::StringOrNumber(..) => {
                             StandardOpCode::argument_reporter_string_number
                         }
-                        CustomBlockInputType::Boolean(_) => {
+                        CustomBlockInputType::Boolean(..) => {
                             StandardOpCode::argument_reporter_boolean
                         }
                     });
                     b.add_field(
                         "VALUE",
-                        BlockFieldBuilder::new_with_kind(
-                            ty.name(),
-                            crate::prelude::FieldKind::NoRefMaybe,
-                        ),
+                        BlockFieldBuilder::new_with_kind(ty.name(), FieldKind::NoRefMaybe),
                     );
                     b.set_shadow(true);
                     b
@@ -125,21 +136,234 @@ impl CustomBlockBuilder {
     }
 }
 
+/// Returned from [`TargetBuilder::define_custom_block`](crate::target::TargetBuilder::define_custom_block)
+/// for building calls to the custom block and its argument reporters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomBlockHandle {
+    ty: CustomBlockTy,
+}
+
+impl CustomBlockHandle {
+    pub fn new(ty: CustomBlockTy) -> CustomBlockHandle {
+        CustomBlockHandle { ty }
+    }
+
+    pub fn ty(&self) -> &CustomBlockTy {
+        &self.ty
+    }
+
+    /// Arguments that isn't given uses their default.
+    pub fn call<K, I>(&self, args: I) -> StackBuilder
+    where
+        K: Into<String>,
+        I: IntoIterator<Item = (K, BlockInputBuilder)>,
+    {
+        let mut call = CustomFuncCallBuilder::new();
+        call.set_proccode(self.ty.proccode());
+        for (key, input) in args {
+            call.add_input(key, input);
+        }
+        StackBuilder::start_with_capacity(1, BlockBuilder::CustomBlockCall(call))
+    }
+
+    /// Name of the variable that [`CustomBlockHandle::ret`] sets
+    pub fn return_variable(&self) -> String {
+        return_variable(&self.ty.proccode())
+    }
+
+    /// Set the return value and stop the custom block, for using inside the definition.
+    ///
+    /// Calls put in an input reports this value, see [`lower`](crate::lower).
+    pub fn ret(&self, value: BlockInputBuilder) -> StackBuilder {
+        set_var_to(
+            BlockFieldBuilder::new_with_kind(self.return_variable(), FieldKind::SpriteVariable),
+            value,
+        )
+        .next(stop(
+            BlockFieldBuilder::new("this script".to_owned()),
+            false,
+        ))
+    }
+
+    /// See [`CustomBlockTy::local`]
+    pub fn local(&self, name: &str) -> Option<StackBuilder> {
+        self.ty.local(name)
+    }
+
+    /// See [`CustomBlockTy::set_local`]
+    pub fn set_local(&self, name: &str, value: BlockInputBuilder) -> Option<StackBuilder> {
+        self.ty.set_local(name, value)
+    }
+
+    /// Reporter of the argument, for using inside the definition.
+    ///
+    /// [`None`] if the custom block doesn't have this argument.
+    pub fn arg(&self, name: &str) -> Option<StackBuilder> {
+        self.ty
+            .vars()
+            .into_iter()
+            .find(|(_, ty)| ty.name() == name)
+            .map(|(_, ty)| match ty {
+                CustomBlockInputType::Boolean(name, _) => custom_block_var_boolean(name),
+                _ => custom_block_var_string_number(name),
+            })
+    }
+}
+
+/// Variable that a custom block's return value is put in when it's used as a reporter.
+///
+/// Every target that defines or calls the custom block gets its own one.
+pub fn return_variable(proccode: &str) -> String {
+    format!("{proccode} (return)")
+}
+
+/// List that results of custom block calls are copied into while the block using them
+/// still has more calls to run, see [`lower`](crate::lower).
+pub const RESULTS_LIST: &str = "(custom block results)";
+
+/// List that a custom block's [locals](CustomBlockTy::add_local) are kept in.
+pub fn locals_list(proccode: &str) -> String {
+    format!("{proccode} (locals)")
+}
+
+/// Custom block in [`ProjectBuilder`](crate::project::ProjectBuilder)'s library
+///
+/// It's copied into every target that calls it when the project is built.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedCustomBlock {
+    pub signature: CustomBlockTy,
+    /// Script under the define block
+    pub body: StackBuilder,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum CustomBlockInputType {
     Text(String),
-    StringOrNumber(String),
-    Boolean(String),
+    /// Name and the value a call gets when it leaves the argument out
+    StringOrNumber(String, Value),
+    /// Name and the value a call gets when it leaves the argument out
+    Boolean(String, bool),
 }
 
 impl CustomBlockInputType {
+    /// String or number argument that defaults to empty
+    pub fn string_or_number<S: Into<String>>(name: S) -> CustomBlockInputType {
+        CustomBlockInputType::StringOrNumber(name.into(), Value::Text("".into()))
+    }
+
+    /// Boolean argument that defaults to false
+    pub fn boolean<S: Into<String>>(name: S) -> CustomBlockInputType {
+        CustomBlockInputType::Boolean(name.into(), false)
+    }
+
     pub fn name(&self) -> String {
         match self {
             CustomBlockInputType::Text(name) => name.clone(),
-            CustomBlockInputType::StringOrNumber(name) => name.clone(),
-            CustomBlockInputType::Boolean(name) => name.clone(),
+            CustomBlockInputType::StringOrNumber(name, _) => name.clone(),
+            CustomBlockInputType::Boolean(name, _) => name.clone(),
+        }
+    }
+
+    /// [`None`] for [`CustomBlockInputType::Text`]
+    pub fn default_value(&self) -> Option<ValueWithBool> {
+        match self {
+            CustomBlockInputType::Text(_) => None,
+            CustomBlockInputType::StringOrNumber(_, Value::Number(value)) => {
+                Some(ValueWithBool::Number(value.clone()))
+            }
+            CustomBlockInputType::StringOrNumber(_, Value::Text(value)) => {
+                Some(ValueWithBool::Text(value.clone()))
+            }
+            CustomBlockInputType::Boolean(_, value) => Some(ValueWithBool::Bool(*value)),
+        }
+    }
+}
+
+/// Error from [`CustomBlockTy::parse`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseSignatureError {
+    /// There's nothing in the signature
+    Empty,
+    /// `(` or `<` without its closing bracket
+    Unclosed(char),
+    /// `()` or `<>`
+    EmptyArgumentName,
+}
+
+impl std::fmt::Display for ParseSignatureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseSignatureError::Empty => write!(f, "custom block signature is empty"),
+            ParseSignatureError::Unclosed(c) => write!(f, "`{c}` in signature is never closed"),
+            ParseSignatureError::EmptyArgumentName => {
+                write!(f, "argument in signature doesn't have a name")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseSignatureError {}
+
+enum ProccodePart {
+    Label(String),
+    Arg { name: Option<String>, boolean: bool },
+}
+
+/// Split a proccode into labels and arguments, with `\%` unescaped.
+///
+/// With `brackets`, `(name)` and `<name>` are also arguments and `\(` and `\<` are escapes
+/// as in [`CustomBlockTy::parse`].
+fn split_proccode(s: &str, brackets: bool) -> Result<Vec<ProccodePart>, ParseSignatureError> {
+    let mut parts = vec![];
+    let mut label = String::new();
+    let flush = |label: &mut String, parts: &mut Vec<ProccodePart>| {
+        let collapsed = label.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !collapsed.is_empty() {
+            parts.push(ProccodePart::Label(collapsed));
+        }
+        label.clear();
+    };
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if matches!(chars.peek(), Some('%'))
+                || brackets && matches!(chars.peek(), Some('(' | '<' | '\\')) =>
+            {
+                label.extend(chars.next());
+            }
+            '%' if matches!(chars.peek(), Some('s' | 'n' | 'b')) => {
+                flush(&mut label, &mut parts);
+                let boolean = chars.next() == Some('b');
+                parts.push(ProccodePart::Arg {
+                    name: None,
+                    boolean,
+                });
+            }
+            '(' | '<' if brackets => {
+                flush(&mut label, &mut parts);
+                let close = if c == '(' { ')' } else { '>' };
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some(n) if n == close => break,
+                        Some(n) => name.push(n),
+                        None => return Err(ParseSignatureError::Unclosed(c)),
+                    }
+                }
+                let name = name.trim();
+                if name.is_empty() {
+                    return Err(ParseSignatureError::EmptyArgumentName);
+                }
+                parts.push(ProccodePart::Arg {
+                    name: Some(name.to_owned()),
+                    boolean: c == '<',
+                });
+            }
+            c => label.push(c),
         }
     }
+    flush(&mut label, &mut parts);
+    Ok(parts)
 }
 
 // pub type CustomBlockTy = (BlockMutationEnum, HashMap<String, Uid>);
@@ -147,28 +371,134 @@ impl CustomBlockInputType {
 pub struct CustomBlockTy {
     ty: Vec<(Option<String>, CustomBlockInputType)>,
     warp: bool,
+    /// See [`CustomBlockTy::add_local`]
+    locals: Vec<String>,
 }
 
 impl CustomBlockTy {
     pub fn new(args: Vec<CustomBlockInputType>, warp: bool) -> CustomBlockTy {
         let ty = args
-            .iter()
-            .map(|arg| {
-                let name = match arg {
-                    CustomBlockInputType::Text(_) => None,
-                    _ => Some(Uid::generate().into_inner()),
-                };
-                (name, arg.clone())
+            .into_iter()
+            .map(|arg| match arg {
+                CustomBlockInputType::Text(label) => (
+                    None,
+                    CustomBlockInputType::Text(
+                        label.split_whitespace().collect::<Vec<_>>().join(" "),
+                    ),
+                ),
+                arg => (Some(Uid::generate().into_inner()), arg),
             })
             .collect::<Vec<_>>();
-        CustomBlockTy { ty, warp }
+        CustomBlockTy {
+            ty,
+            warp,
+            locals: vec![],
+        }
     }
 
-    pub fn name(&self) -> String {
-        match (&self.ty).into_iter().next() {
-            Some((_, CustomBlockInputType::Text(name))) => name.clone(),
-            _ => "".into(),
+    /// Read back the signature from a `procedures_prototype`'s mutation, keeping its argument ids.
+    ///
+    /// [`None`] if it's not a prototype mutation or the arguments doesn't match the proccode.
+    pub fn from_mutation(mutation: &BlockMutationEnum) -> Option<CustomBlockTy> {
+        let BlockMutationEnum::ProceduresPrototype {
+            proccode,
+            argumentids,
+            argumentnames,
+            argumentdefaults,
+            warp,
+        } = mutation
+        else {
+            return None;
+        };
+        let mut args = argumentids.iter().zip(argumentnames);
+        let mut defaults = argumentdefaults.iter();
+        let mut ty = vec![];
+        for part in split_proccode(proccode, false).ok()? {
+            ty.push(match part {
+                ProccodePart::Label(label) => (None, CustomBlockInputType::Text(label)),
+                ProccodePart::Arg { boolean, .. } => {
+                    let (id, name) = args.next()?;
+                    let default = defaults.next();
+                    let arg = if boolean {
+                        CustomBlockInputType::Boolean(
+                            name.clone(),
+                            matches!(default, Some(ValueWithBool::Bool(true)))
+                                || matches!(default, Some(ValueWithBool::Text(t)) if t == "true"),
+                        )
+                    } else {
+                        CustomBlockInputType::StringOrNumber(
+                            name.clone(),
+                            match default {
+                                Some(ValueWithBool::Number(n)) => Value::Number(n.clone()),
+                                Some(ValueWithBool::Text(t)) => Value::Text(t.clone()),
+                                Some(ValueWithBool::Bool(b)) => Value::Text(b.to_string()),
+                                None => Value::Text("".into()),
+                            },
+                        )
+                    };
+                    (Some(id.clone()), arg)
+                }
+            });
         }
+        if args.next().is_some() {
+            return None;
+        }
+        Some(CustomBlockTy {
+            ty,
+            warp: warp.unwrap_or_default(),
+            locals: vec![],
+        })
+    }
+
+    /// Parse a signature where labels and arguments can be anywhere.
+    ///
+    /// Arguments are written as either:
+    ///  - `%s` and `%b`, named `arg1`, `arg2`, ... by their position among the arguments.
+    ///  - `(name)` for a string or number and `<name>` for a boolean.
+    ///
+    /// Put `\` before `%`, `(` or `<` to have it in the label as is.
+    /// Whitespace in labels is collapsed to a single space, like the editor does.
+    ///
+    /// ```
+    /// # use sb_itchy::custom_block::CustomBlockTy;
+    /// let a = CustomBlockTy::parse("move %s steps and jump %b", false).unwrap();
+    /// let b = CustomBlockTy::parse("move (steps) steps and jump <high>", false).unwrap();
+    /// assert_eq!(a.proccode(), "move %s steps and jump %b");
+    /// assert_eq!(a.proccode(), b.proccode());
+    /// assert_eq!(b.argumentnames(), ["steps", "high"]);
+    /// ```
+    pub fn parse(signature: &str, warp: bool) -> Result<CustomBlockTy, ParseSignatureError> {
+        let parts = split_proccode(signature, true)?;
+        if parts.is_empty() {
+            return Err(ParseSignatureError::Empty);
+        }
+        Ok(CustomBlockTy::from_parts(parts, warp))
+    }
+
+    /// Signature of a proccode that the target doesn't have, so the blocks using it still build.
+    /// See [`ValidationError::UnknownCustomBlock`](crate::validate::ValidationError::UnknownCustomBlock).
+    pub(crate) fn from_proccode(proccode: &str) -> CustomBlockTy {
+        CustomBlockTy::from_parts(split_proccode(proccode, false).unwrap_or_default(), false)
+    }
+
+    fn from_parts(parts: Vec<ProccodePart>, warp: bool) -> CustomBlockTy {
+        let mut arg_count = 0;
+        let args = parts
+            .into_iter()
+            .map(|part| match part {
+                ProccodePart::Label(label) => CustomBlockInputType::Text(label),
+                ProccodePart::Arg { name, boolean } => {
+                    arg_count += 1;
+                    let name = name.unwrap_or_else(|| format!("arg{arg_count}"));
+                    if boolean {
+                        CustomBlockInputType::boolean(name)
+                    } else {
+                        CustomBlockInputType::string_or_number(name)
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        CustomBlockTy::new(args, warp)
     }
 
     pub fn call_mutation(&self) -> BlockMutationEnum {
@@ -201,9 +531,9 @@ impl CustomBlockTy {
         let mut proccode: Option<String> = None;
         for (_, ty) in self.ty.iter() {
             let ty = match ty {
-                CustomBlockInputType::Text(name) => name.clone(),
-                CustomBlockInputType::StringOrNumber(_) => "%s".into(),
-                CustomBlockInputType::Boolean(_) => "%b".into(),
+                CustomBlockInputType::Text(label) => label.replace('%', "\\%"),
+                CustomBlockInputType::StringOrNumber(..) => "%s".into(),
+                CustomBlockInputType::Boolean(..) => "%b".into(),
             };
             match &mut proccode {
                 Some(proccode) => {
@@ -244,17 +574,154 @@ impl CustomBlockTy {
     pub fn argumentdefaults(&self) -> Vec<ValueWithBool> {
         self.vars()
             .iter()
-            .map(|(_, i)| match i {
-                CustomBlockInputType::Text(_) => unreachable!(),
-                CustomBlockInputType::StringOrNumber(_) => ValueWithBool::Text("".into()),
-                CustomBlockInputType::Boolean(_) => ValueWithBool::Bool(false),
-            })
+            .filter_map(|(_, i)| i.default_value())
             .collect::<Vec<_>>()
     }
 
     pub fn warp(&self) -> bool {
         self.warp
     }
+
+    /// Add a local variable that every call of the custom block has its own copy of,
+    /// so it still works when the custom block calls itself.
+    ///
+    /// Locals are kept in a list named by [`locals_list`] with one frame of items per running call.
+    /// The frame is added when the definition starts and removed when it ends
+    /// or before any `stop this script` in it, like the one in [`CustomBlockHandle::ret`].
+    /// Locals start empty.
+    pub fn add_local<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.locals.push(name.into());
+        self
+    }
+
+    pub fn locals(&self) -> &[String] {
+        &self.locals
+    }
+
+    fn locals_field(&self) -> BlockFieldBuilder {
+        BlockFieldBuilder::new_with_kind(locals_list(&self.proccode()), FieldKind::SpriteList)
+    }
+
+    /// Blocks that add this call's frame, empty if there are no locals
+    pub fn push_locals(&self) -> StackBuilder {
+        let mut stack = StackBuilder::with_capacity(self.locals.len());
+        for _ in &self.locals {
+            stack = stack.next(add_to_list(
+                self.locals_field(),
+                BlockInputBuilder::value(BlockInputValue::String {
+                    value: "".to_owned().into(),
+                }),
+            ));
+        }
+        stack
+    }
+
+    /// Blocks that remove this call's frame, empty if there are no locals
+    pub fn pop_locals(&self) -> StackBuilder {
+        let mut stack = StackBuilder::with_capacity(self.locals.len());
+        for _ in &self.locals {
+            stack = stack.next(delete_in_list(
+                self.locals_field(),
+                BlockInputBuilder::stack(length_of_list(self.locals_field())),
+            ));
+        }
+        stack
+    }
+
+    /// `body` of the definition with [`CustomBlockTy::pop_locals`] before every `stop this script`
+    /// in it and at the end, unless it ends with a cap block.
+    pub fn pop_locals_at_exits(&self, mut body: StackBuilder) -> StackBuilder {
+        if self.locals.is_empty() {
+            return body;
+        }
+        self.pop_locals_before_stops(&mut body);
+        let ends_with_cap = body
+            .stack
+            .last()
+            .and_then(BlockBuilder::shape)
+            .is_some_and(|shape| !shape.has_next());
+        if ends_with_cap {
+            body
+        } else {
+            body.next(self.pop_locals())
+        }
+    }
+
+    fn pop_locals_before_stops(&self, stack: &mut StackBuilder) {
+        for mut block in std::mem::take(&mut stack.stack) {
+            if let BlockBuilder::Normal(n) = &mut block {
+                for (_, input) in n
+                    .inputs_mut()
+                    .iter_mut()
+                    .filter(|(name, _)| name.starts_with("SUBSTACK"))
+                {
+                    for value in &mut input.values {
+                        if let Some(StackOrValue::Stack(substack)) = value {
+                            self.pop_locals_before_stops(substack);
+                        }
+                    }
+                }
+                let stops_script = n.opcode() == &OpCode::Standard(StandardOpCode::control_stop)
+                    && n.fields()
+                        .get("STOP_OPTION")
+                        .is_some_and(|option| option.value == "this script");
+                if stops_script {
+                    stack.stack.extend(self.pop_locals().stack);
+                }
+            }
+            stack.stack.push(block);
+        }
+    }
+
+    /// Index of the local in the locals list for the running call
+    fn local_index(&self, name: &str) -> Option<BlockInputBuilder> {
+        let position = self.locals.iter().position(|local| local == name)?;
+        let from_end = self.locals.len() - 1 - position;
+        let length = length_of_list(self.locals_field());
+        Some(BlockInputBuilder::stack(if from_end == 0 {
+            length
+        } else {
+            sub(
+                BlockInputBuilder::stack(length),
+                BlockInputBuilder::value(BlockInputValue::Number {
+                    value: (from_end as f64).into(),
+                }),
+            )
+        }))
+    }
+
+    /// Reporter of the local, [`None`] if there's no local with this name.
+    pub fn local(&self, name: &str) -> Option<StackBuilder> {
+        Some(item_in_list(self.locals_field(), self.local_index(name)?))
+    }
+
+    /// [`None`] if there's no local with this name.
+    pub fn set_local(&self, name: &str, value: BlockInputBuilder) -> Option<StackBuilder> {
+        Some(replace_in_list(
+            self.locals_field(),
+            self.local_index(name)?,
+            value,
+        ))
+    }
+
+    /// Same signature with new argument ids
+    pub fn with_new_ids(&self) -> CustomBlockTy {
+        let ty = self
+            .ty
+            .iter()
+            .map(|(id, arg)| {
+                (
+                    id.as_ref().map(|_| Uid::generate().into_inner()),
+                    arg.clone(),
+                )
+            })
+            .collect();
+        CustomBlockTy {
+            ty,
+            warp: self.warp,
+            locals: self.locals.clone(),
+        }
+    }
 }
 
 // pub fn generate_func_input_block(
@@ -458,7 +925,7 @@ impl CustomBlockTy {
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct CustomFuncCallBuilder {
-    name: String,
+    proccode: String,
     comment: Option<CommentBuilder>,
     pub(crate) args: Vec<(String, BlockInputBuilder)>,
     x: Option<f64>,
@@ -487,8 +954,9 @@ impl CustomFuncCallBuilder {
         self
     }
 
-    pub fn set_name(&mut self, name: String) -> &mut Self {
-        self.name = name;
+    /// `proccode` is the one from [`CustomBlockTy::proccode`]
+    pub fn set_proccode(&mut self, proccode: String) -> &mut Self {
+        self.proccode = proccode;
         self
     }
 
@@ -508,6 +976,18 @@ impl CustomFuncCallBuilder {
         self
     }
 
+    pub fn pos(&self) -> (Option<f64>, Option<f64>) {
+        (self.x, self.y)
+    }
+
+    pub fn proccode(&self) -> &str {
+        &self.proccode
+    }
+
+    pub fn comment_mut(&mut self) -> Option<&mut CommentBuilder> {
+        self.comment.as_mut()
+    }
+
     pub fn build(
         self,
         my_uid: &Uid,
@@ -516,19 +996,14 @@ impl CustomFuncCallBuilder {
         target_context: &TargetContext,
     ) -> BlockNormal {
         let CustomFuncCallBuilder {
-            name,
+            proccode,
             comment,
             args,
             x,
             y,
         } = self;
 
-        let ty = target_context
-            .custom_blocks
-            .iter()
-            .find(|ty| ty.name() == name)
-            .expect("Custom function not found")
-            .clone();
+        let ty = target_context.custom_block(&proccode);
 
         let mut call_block = BlockNormalBuilder::new(StandardOpCode::procedures_call);
         // for (key, input) in args {
@@ -538,25 +1013,24 @@ impl CustomFuncCallBuilder {
         //     call_block.add_input(arg_id.into_inner(), input);
         // }
         for (id, name) in ty.vars() {
-            let name = name.name();
-            let arg = args.iter().find(|(key, _)| key == &name);
-            let arg = match arg {
-                Some((_, arg)) => arg.clone(),
-                None => {
-                    let defaults = ty.argumentdefaults();
-                    let pos = ty.argumentnames().iter().position(|i| i == &name).unwrap();
-                    let default = defaults[pos].clone();
-
-                    BlockInputBuilder::value(match default {
-                        ValueWithBool::Text(value) => BlockInputValue::String {
-                            value: value.into(),
-                        },
-                        ValueWithBool::Number(value) => BlockInputValue::Number {
-                            value: value.into(),
-                        },
-                        ValueWithBool::Bool(_) => continue,
+            let arg = args.iter().find(|(key, _)| key == &name.name());
+            let arg = match (arg, name.default_value()) {
+                (Some((_, arg)), _) => arg.clone(),
+                (None, Some(ValueWithBool::Text(value))) => {
+                    BlockInputBuilder::value(BlockInputValue::String {
+                        value: value.into(),
+                    })
+                }
+                (None, Some(ValueWithBool::Number(value))) => {
+                    BlockInputBuilder::value(BlockInputValue::Number {
+                        value: value.into(),
                     })
                 }
+                // An empty boolean input is already false and true is `not <>`
+                (None, Some(ValueWithBool::Bool(true))) => BlockInputBuilder::stack(
+                    StackBuilder::start(BlockNormalBuilder::new(StandardOpCode::operator_not)),
+                ),
+                (None, _) => continue,
             };
             call_block.add_input(id.into_inner(), arg);
         }
@@ -40,7 +40,7 @@ pub fn write_zip<W: Write + Seek>(
     project: ProjectBuilder,
 ) -> Result<(), zip::result::ZipError> {
     let mut res_buf = vec![];
-    let project = project.build(&mut res_buf);
+    let project = project.build_json(&mut res_buf);
     let mut zip = zip::ZipWriter::new(writer);
     for mut res in res_buf {
         zip.start_file(
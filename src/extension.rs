@@ -0,0 +1,169 @@
+//! Blocks from extensions that this crate doesn't know about
+//!
+//! Describe the extension with [`define_extension!`](crate::define_extension) or implement [`ExtensionDef`] yourself,
+//! then register it with [`ProjectBuilder::add_extension`](crate::project::ProjectBuilder::add_extension)
+//! so the editor knows where to load it from.
+//!
+//! ```
+//! # use sb_itchy::{blocks::say, prelude::*};
+//! # type Bib = BlockInputBuilder;
+//! sb_itchy::define_extension! {
+//!     /// TurboWarp's fetch extension
+//!     pub struct Fetch {
+//!         id: "fetch",
+//!         url: "https://extensions.turbowarp.org/fetch.js",
+//!     }
+//!
+//!     /// <br/>
+//!     /// `url` is the website to get
+//!     pub fn get(url: "URL" Text) -> Reporter "fetch_get" "GET [URL]";
+//! }
+//!
+//! # let mut project = ProjectBuilder::default();
+//! # let mut sprite = SpriteBuilder::default();
+//! # let url = Bib::value(BlockInputValue::String { value: "https://example.com".to_owned().into() });
+//! project.add_extension(&Fetch);
+//! sprite.target.add_block_stack(say(Bib::stack(Fetch::get(url))));
+//! # project.add_sprite(sprite);
+//! # let json = project.build_json(&mut vec![]);
+//! # assert_eq!(json["extensionURLs"]["fetch"], "https://extensions.turbowarp.org/fetch.js");
+//! # assert_eq!(json["extensions"][0], "fetch");
+//! ```
+
+use crate::registry::OpCodeInfo;
+
+/// An extension and the blocks in it
+pub trait ExtensionDef {
+    /// Prefix of every opcode in the extension. Like `"pen"` in `pen_clear`.
+    fn id(&self) -> &'static str;
+
+    /// Where the editor loads the extension from.
+    /// [`None`] for extensions that comes with the editor.
+    fn url(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Every block in the extension, menus included
+    fn blocks(&self) -> &'static [OpCodeInfo];
+
+    fn info(&self) -> ExtensionInfo {
+        ExtensionInfo {
+            id: self.id(),
+            url: self.url(),
+            blocks: self.blocks(),
+        }
+    }
+}
+
+/// What [`ProjectBuilder`](crate::project::ProjectBuilder) remembers about an [`ExtensionDef`]
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtensionInfo {
+    pub id:     &'static str,
+    pub url:    Option<&'static str>,
+    pub blocks: &'static [OpCodeInfo],
+}
+
+impl ExtensionInfo {
+    pub fn block(&self, opcode: &str) -> Option<&'static OpCodeInfo> {
+        self.blocks.iter().find(|info| info.opcode == opcode)
+    }
+}
+
+/// Define an [`ExtensionDef`] and a builder function for each of its blocks
+///
+/// Every block is written as
+/// `fn name(input: "INPUT" InputKind, ...; field: "FIELD" FieldMenu, ...) -> BlockShape "opcode" "label";`
+/// where the kinds are variants of [`InputKind`](crate::registry::InputKind),
+/// [`FieldMenu`](crate::registry::FieldMenu) and [`BlockShape`](crate::registry::BlockShape).
+/// Inputs comes before the `;` and fields after it.
+///
+/// Builder functions are associated functions of the struct and works just like the ones in [`crate::blocks`].
+/// Blocks with `_menu_` in the opcode are built as shadow, that's how Scratch names extension menus.
+#[macro_export]
+macro_rules! define_extension {
+    (@url) => { None };
+    (@url $url:literal) => { Some($url) };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            id: $id:literal
+            $(, url: $url:literal)?
+            $(,)?
+        }
+
+        $(
+            $(#[$fn_meta:meta])*
+            $fn_vis:vis fn $fn_name:ident(
+                $($input:ident: $input_name:literal $input_kind:expr),*
+                $(; $($field:ident: $field_name:literal $field_menu:expr),*)?
+            ) -> $shape:ident $opcode:literal $label:literal;
+        )*
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+        $vis struct $name;
+
+        impl $name {
+            $(
+                $(#[$fn_meta])*
+                $fn_vis fn $fn_name(
+                    $($input: $crate::block::BlockInputBuilder,)*
+                    $($($field: $crate::block::BlockFieldBuilder,)*)?
+                ) -> $crate::stack::StackBuilder {
+                    $crate::stack::StackBuilder::start({
+                        let mut b = $crate::block::BlockNormalBuilder::new($opcode);
+                        $(b.add_input($input_name, $input);)*
+                        $($(b.add_field($field_name, $field);)*)?
+                        b.set_shadow($opcode.contains("_menu_"));
+                        b
+                    })
+                }
+            )*
+        }
+
+        impl $crate::extension::ExtensionDef for $name {
+            fn id(&self) -> &'static str {
+                $id
+            }
+
+            fn url(&self) -> Option<&'static str> {
+                $crate::define_extension!(@url $($url)?)
+            }
+
+            fn blocks(&self) -> &'static [$crate::registry::OpCodeInfo] {
+                static BLOCKS: &[$crate::registry::OpCodeInfo] = &[
+                    $(
+                        $crate::registry::OpCodeInfo {
+                            opcode: $opcode,
+                            shape: $crate::registry::BlockShape::$shape,
+                            label: $label,
+                            inputs: &[$(
+                                $crate::registry::InputInfo {
+                                    name: $input_name,
+                                    kind: {
+                                        #[allow(unused_imports)]
+                                        use $crate::registry::InputKind::*;
+                                        $input_kind
+                                    },
+                                },
+                            )*],
+                            fields: &[$($(
+                                $crate::registry::FieldInfo {
+                                    name: $field_name,
+                                    menu: {
+                                        #[allow(unused_imports)]
+                                        use $crate::registry::FieldMenu::*;
+                                        $field_menu
+                                    },
+                                },
+                            )*)?],
+                            extension: Some($id),
+                        },
+                    )*
+                ];
+                BLOCKS
+            }
+        }
+    };
+}
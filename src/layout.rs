@@ -0,0 +1,225 @@
+//! Placing scripts in the workspace
+//!
+//! Stacks that are given a position with [`StackBuilder::set_top_block_position`] are left alone.
+//! Everything else is put into columns from top to bottom, left to right,
+//! using [`StackBuilder::calc_block_height`] for how tall each stack is
+//! and [`StackBuilder::calc_block_width`] for how wide each column is.
+//!
+//! Comments without a position are placed too.
+//! Comments attached to a block goes to the right of the block's script
+//! and workspace comments goes in a column to the right of everything else.
+
+use crate::{
+    block::{BlockBuilder, StackOrValue},
+    comment::CommentBuilder,
+    stack::{BlockHeightData, BlockWidthData, StackBuilder},
+};
+
+/// Height of a minimized comment
+const MINIMIZED_COMMENT_HEIGHT: f64 = 32.;
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutOptions {
+    /// Where the first column starts
+    pub origin_x:          f64,
+    pub origin_y:          f64,
+    /// Horizontal space between columns
+    pub column_gap:        f64,
+    /// Vertical space between stacks in a column
+    pub row_gap:           f64,
+    /// A new column is started when the next stack would go past this.
+    /// A stack taller than this still gets a column to itself.
+    pub max_column_height: f64,
+    pub height_data:       BlockHeightData,
+    pub width_data:        BlockWidthData,
+    /// Space between a script and its comments and between each comment
+    pub comment_gap:       f64,
+    /// Comments with more characters than this are minimized
+    pub minimize_comments: Option<usize>,
+}
+
+impl LayoutOptions {
+    pub fn set_origin(&mut self, x: f64, y: f64) -> &mut Self {
+        self.origin_x = x;
+        self.origin_y = y;
+        self
+    }
+
+    pub fn set_column_gap(&mut self, column_gap: f64) -> &mut Self {
+        self.column_gap = column_gap;
+        self
+    }
+
+    pub fn set_row_gap(&mut self, row_gap: f64) -> &mut Self {
+        self.row_gap = row_gap;
+        self
+    }
+
+    pub fn set_max_column_height(&mut self, max_column_height: f64) -> &mut Self {
+        self.max_column_height = max_column_height;
+        self
+    }
+
+    pub fn set_height_data(&mut self, height_data: BlockHeightData) -> &mut Self {
+        self.height_data = height_data;
+        self
+    }
+
+    pub fn set_width_data(&mut self, width_data: BlockWidthData) -> &mut Self {
+        self.width_data = width_data;
+        self
+    }
+
+    pub fn set_comment_gap(&mut self, comment_gap: f64) -> &mut Self {
+        self.comment_gap = comment_gap;
+        self
+    }
+
+    pub fn set_minimize_comments(&mut self, length: Option<usize>) -> &mut Self {
+        self.minimize_comments = length;
+        self
+    }
+
+    /// Give a position to every stack and comment that doesn't have one.
+    ///
+    /// `workspace_comments` are comments that isn't attached to any block.
+    pub fn arrange(&self, stacks: &mut [StackBuilder], workspace_comments: &mut [CommentBuilder]) {
+        self.arrange_stacks(stacks);
+
+        let mut right_edge = self.origin_x;
+        for stack in stacks.iter_mut() {
+            let (x, y) = stack.top_block_position();
+            let (x, y) = (x.unwrap_or_default(), y.unwrap_or_default());
+            let comment_x = x + stack.calc_block_width(&self.width_data) + self.comment_gap;
+            right_edge = right_edge.max(comment_x);
+            let mut next_free_y = y;
+            self.place_attached_comments(stack, comment_x, y, &mut next_free_y, &mut right_edge);
+        }
+
+        let mut next_free_y = self.origin_y;
+        for comment in workspace_comments {
+            self.place_comment(
+                comment,
+                right_edge + self.column_gap,
+                self.origin_y,
+                &mut next_free_y,
+            );
+        }
+    }
+
+    fn arrange_stacks(&self, stacks: &mut [StackBuilder]) {
+        let mut x = self.origin_x;
+        let mut y = self.origin_y;
+        let mut column_width: f64 = 0.;
+        for stack in stacks.iter_mut().filter(|stack| !stack.has_position()) {
+            let height = stack.calc_block_height(&self.height_data, false);
+            let width = stack.calc_block_width(&self.width_data);
+            if y > self.origin_y && y + height > self.origin_y + self.max_column_height {
+                x += column_width + self.column_gap;
+                y = self.origin_y;
+                column_width = 0.;
+            }
+            stack.set_top_block_position(x, y);
+            y += height + self.row_gap;
+            column_width = column_width.max(width);
+        }
+    }
+
+    /// `y` is where the top of the stack is
+    /// and `next_free_y` is where the comment above ended.
+    fn place_attached_comments(
+        &self,
+        stack: &mut StackBuilder,
+        comment_x: f64,
+        mut y: f64,
+        next_free_y: &mut f64,
+        right_edge: &mut f64,
+    ) {
+        for block in &mut stack.stack {
+            let height = block.calc_block_height(&self.height_data, false);
+            if let Some(comment) = block.comment_mut() {
+                self.place_comment(comment, comment_x, y, next_free_y);
+                *right_edge = right_edge.max(comment_x + comment.width as f64);
+            }
+
+            // Reporters are on the same row as their block and blocks in C-blocks are below it.
+            let mut inputs: Vec<(&String, &mut _)> = match block {
+                BlockBuilder::Normal(n) => n.inputs_mut().iter_mut().collect(),
+                BlockBuilder::CustomBlockCall(c) => c
+                    .args
+                    .iter_mut()
+                    .map(|(name, input)| (&*name, input))
+                    .collect(),
+                BlockBuilder::CustomBlock(_) | BlockBuilder::VarList(_) => vec![],
+            };
+            inputs.sort_by(|(a, _), (b, _)| a.cmp(b));
+            let mut substack_y = y + self.height_data.block_height;
+            for (name, input) in inputs {
+                let input_y = if name.starts_with("SUBSTACK") {
+                    let input_y = substack_y;
+                    // `SUBSTACK2` starts below `SUBSTACK` and the `else` row
+                    substack_y += self.height_data.block_nest_height;
+                    for value in input.values.iter().flatten() {
+                        if let StackOrValue::Stack(stack) = value {
+                            substack_y += stack.calc_block_height(&self.height_data, false);
+                        }
+                    }
+                    input_y
+                } else {
+                    y
+                };
+                for value in &mut input.values {
+                    if let Some(StackOrValue::Stack(stack)) = value {
+                        self.place_attached_comments(
+                            stack,
+                            comment_x,
+                            input_y,
+                            next_free_y,
+                            right_edge,
+                        );
+                    }
+                }
+            }
+
+            y += height;
+        }
+    }
+
+    /// Comments that already has a position are only minimized.
+    fn place_comment(&self, comment: &mut CommentBuilder, x: f64, y: f64, next_free_y: &mut f64) {
+        if let Some(max_length) = self.minimize_comments {
+            if comment.content.chars().count() > max_length {
+                comment.minimized = true;
+            }
+        }
+        if comment.x.is_some() || comment.y.is_some() {
+            return;
+        }
+        let y = y.max(*next_free_y);
+        comment.set_pos(Some(x), Some(y));
+        let height = if comment.minimized {
+            MINIMIZED_COMMENT_HEIGHT
+        } else {
+            comment.height as f64
+        };
+        *next_free_y = y + height + self.comment_gap;
+    }
+}
+
+impl Default for LayoutOptions {
+    #[rustfmt::skip]
+    fn default() -> Self {
+        LayoutOptions {
+            origin_x:          0.,
+            origin_y:          0.,
+            column_gap:        96.,
+            row_gap:           64.,
+            max_column_height: 2000.,
+            height_data:       BlockHeightData::default(),
+            width_data:        BlockWidthData::default(),
+            comment_gap:       32.,
+            minimize_comments: None,
+        }
+    }
+}
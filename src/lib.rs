@@ -1,6 +1,6 @@
 //! Hello there!
 //! Nothing much here be here are some note when building:
-//!  - Scratch won't load if there are layer order collision; make sure all your Sprite have different layer order; Stage should be 0.
+//!  - Scratch won't load if there are layer order collision; [`ProjectBuilder`](project::ProjectBuilder) renumbers them when building so this is taken care of.
 //!  - Scratch also won't load if there are no costume in Sprite or Stage; make sure to have atleast one!
 //!
 //! More documentation will made later if a lot of people actually uses this crate.
@@ -12,13 +12,21 @@ pub mod block;
 pub mod comment;
 pub mod custom_block;
 pub mod data;
+pub mod extension;
+pub mod layout;
+pub mod lower;
+pub mod monitor;
 pub mod project;
+pub mod script;
 pub mod stack;
 pub mod target;
+pub mod typed_stack;
 
 pub mod opcode;
+pub mod registry;
 pub mod resource;
 pub mod uid;
+pub mod validate;
 
 pub mod build_context;
 
@@ -38,12 +46,17 @@ pub mod prelude {
         build_context::{GlobalVarListContext, TargetContext},
         comment::CommentBuilder,
         data::{ListBuilder, VariableBuilder},
-        opcode::StandardOpCode,
+        extension::{ExtensionDef, ExtensionInfo},
+        layout::LayoutOptions,
+        monitor::{MonitorBuilder, MonitorMode, MonitorOf, MonitorReporter},
+        opcode::{OpCode, PenExtensionOpCode, StandardOpCode},
         project::ProjectBuilder,
         resource::{Resource, ResourceError},
+        script::{script, Expr, Script},
         stack::StackBuilder,
         target::{SpriteBuilder, StageBuilder, TargetBuilder},
         uid::Uid,
+        validate::{Severity, ValidationError, Validator},
     };
     use super::*;
     pub use custom_block::*;
@@ -0,0 +1,318 @@
+//! Custom blocks used as reporters
+//!
+//! Scratch custom blocks can't report anything, so a custom block call put in an input is
+//! lowered into a `procedures_call` above the block using it and the input is replaced
+//! with the custom block's return variable, which is set by [`CustomBlockHandle::ret`](crate::custom_block::CustomBlockHandle::ret).
+//! Every target has its own return variables, even for custom blocks that the stage also has.
+//!
+//! Calls are hoisted in the order Scratch would evaluate them: inputs in the order they appear on
+//! the block and the arguments of a call before the call itself.
+//! When a block uses more than one result, every result but the last is copied into a frame
+//! at the end of the [`RESULTS_LIST`] since the calls after it could overwrite the return variable,
+//! like `fib(n - 1) + fib(n - 2)` does.
+//! The frame is removed after the block runs, or at the start of the branches of `if`
+//! and every time around loops. Running `stop this script` in a C-block that has a frame,
+//! other than `if`, `if else` and loops, leaves the frame in the list.
+//!
+//! Conditions of `repeat until`, `while` and `wait until` are evaluated again at the end of every loop.
+//! `wait until` is turned into an empty `repeat until` for this.
+//! Calls in the inputs of hat blocks aren't lowered.
+
+use crate::{
+    block::{
+        BlockBuilder, BlockFieldBuilder, BlockInputBuilder, BlockNormalBuilder,
+        BlockVarListBuilder, FieldKind, StackOrValue,
+    },
+    blocks::{add_to_list, delete_in_list, item_in_list, length_of_list, sub},
+    custom_block::{return_variable, RESULTS_LIST},
+    opcode::{OpCode, StandardOpCode},
+    stack::StackBuilder,
+};
+
+use sb_sbity::block::BlockInputValue;
+
+/// Lower every reporter call in `stacks`.
+///
+/// Returns the names of the variables and the lists that the lowered stacks use.
+pub(crate) fn lower_reporter_calls(stacks: &mut [StackBuilder]) -> (Vec<String>, Vec<String>) {
+    let mut variables = vec![];
+    let mut uses_results = false;
+    for stack in stacks {
+        lower_stack(stack, &mut variables, &mut uses_results);
+    }
+    let lists = if uses_results {
+        vec![RESULTS_LIST.to_owned()]
+    } else {
+        vec![]
+    };
+    (variables, lists)
+}
+
+fn lower_stack(stack: &mut StackBuilder, variables: &mut Vec<String>, uses_results: &mut bool) {
+    let blocks = std::mem::take(&mut stack.stack);
+    for mut block in blocks {
+        for substack in substacks(&mut block) {
+            lower_stack(substack, variables, uses_results);
+        }
+        if is_hat(&block) {
+            stack.stack.push(block);
+            continue;
+        }
+
+        let mut hoisted = StackBuilder::new();
+        let mut hoist = Hoist {
+            hoisted: &mut hoisted,
+            variables,
+            uses_results,
+        };
+        let frame = hoist.consumer(&mut block);
+        let mut after = pop_results(frame);
+
+        if let BlockBuilder::Normal(n) = &mut block {
+            let is_loop = matches!(
+                n.opcode(),
+                OpCode::Standard(
+                    StandardOpCode::control_repeat_until
+                        | StandardOpCode::control_while
+                        | StandardOpCode::control_wait_until
+                )
+            );
+            let is_if = matches!(
+                n.opcode(),
+                OpCode::Standard(StandardOpCode::control_if | StandardOpCode::control_if_else)
+            );
+            if is_loop && !hoisted.stack.is_empty() {
+                if n.opcode() == &OpCode::Standard(StandardOpCode::control_wait_until) {
+                    n.set_opcode(StandardOpCode::control_repeat_until);
+                }
+                // The condition was checked so this time's frame goes,
+                // and the next check needs the calls again
+                let body = substack(n, "SUBSTACK");
+                body.stack = pop_results(frame)
+                    .next(std::mem::take(body))
+                    .next(hoisted.clone())
+                    .stack;
+            } else if is_if && frame > 0 {
+                // Removing the frame in both branches so it goes before anything in them can stop the script
+                if n.opcode() == &OpCode::Standard(StandardOpCode::control_if) {
+                    n.set_opcode(StandardOpCode::control_if_else);
+                }
+                for name in ["SUBSTACK", "SUBSTACK2"] {
+                    let body = substack(n, name);
+                    body.stack = pop_results(frame).next(std::mem::take(body)).stack;
+                }
+                after = StackBuilder::new();
+            }
+        }
+        stack.stack.append(&mut hoisted.stack);
+        stack.stack.push(block);
+        stack.stack.append(&mut after.stack);
+    }
+}
+
+struct Hoist<'a> {
+    hoisted: &'a mut StackBuilder,
+    variables: &'a mut Vec<String>,
+    uses_results: &'a mut bool,
+}
+
+/// Frame in [`RESULTS_LIST`] of the block whose inputs are being lowered
+struct Frame {
+    /// Results that are copied into the frame
+    size: usize,
+    /// Results copied so far
+    copied: usize,
+}
+
+impl Hoist<'_> {
+    /// Hoist the calls in the inputs of `block`, which is a statement or a call that reads the results when it runs.
+    ///
+    /// Returns the size of the frame that has to be removed after `block` runs.
+    fn consumer(&mut self, block: &mut BlockBuilder) -> usize {
+        let results: usize = expression_inputs(block)
+            .iter()
+            .flat_map(|input| &input.values)
+            .map(|value| match value {
+                Some(StackOrValue::Stack(expression)) => results_used(expression),
+                _ => 0,
+            })
+            .sum();
+        let mut frame = Frame {
+            size: results.saturating_sub(1),
+            copied: 0,
+        };
+        for input in expression_inputs(block) {
+            for value in &mut input.values {
+                if let Some(StackOrValue::Stack(expression)) = value {
+                    self.expression(expression, &mut frame);
+                }
+            }
+        }
+        frame.size
+    }
+
+    fn expression(&mut self, expression: &mut StackBuilder, frame: &mut Frame) {
+        let Some(reporter) = expression.stack.first_mut() else {
+            return;
+        };
+        let BlockBuilder::CustomBlockCall(call) = reporter else {
+            for input in expression_inputs(reporter) {
+                for value in &mut input.values {
+                    if let Some(StackOrValue::Stack(expression)) = value {
+                        self.expression(expression, frame);
+                    }
+                }
+            }
+            return;
+        };
+        let result = return_variable(call.proccode());
+        let call_frame = self.consumer(reporter);
+        let mut call = std::mem::replace(
+            expression,
+            StackBuilder::start_varlist(BlockVarListBuilder::sprite_var(result.clone())),
+        );
+        self.hoisted.stack.append(&mut call.stack);
+        self.hoisted
+            .stack
+            .append(&mut pop_results(call_frame).stack);
+        if !self.variables.contains(&result) {
+            self.variables.push(result);
+        }
+
+        if frame.copied < frame.size {
+            let from_end = frame.size - 1 - frame.copied;
+            frame.copied += 1;
+            *self.uses_results = true;
+            let copy = add_to_list(
+                results_field(),
+                BlockInputBuilder::stack(std::mem::replace(
+                    expression,
+                    item_in_list(results_field(), frame_index(from_end)),
+                )),
+            );
+            self.hoisted.stack.extend(copy.stack);
+        }
+    }
+}
+
+/// Results of calls that the block in `expression` reads when it runs,
+/// calls in the arguments of calls are read by those calls.
+fn results_used(expression: &StackBuilder) -> usize {
+    match expression.stack.first() {
+        Some(BlockBuilder::CustomBlockCall(_)) => 1,
+        Some(BlockBuilder::Normal(n)) => n
+            .inputs()
+            .iter()
+            .filter(|(name, _)| !name.starts_with("SUBSTACK"))
+            .flat_map(|(_, input)| &input.values)
+            .map(|value| match value {
+                Some(StackOrValue::Stack(expression)) => results_used(expression),
+                _ => 0,
+            })
+            .sum(),
+        _ => 0,
+    }
+}
+
+fn results_field() -> BlockFieldBuilder {
+    BlockFieldBuilder::new_with_kind(RESULTS_LIST.to_owned(), FieldKind::SpriteList)
+}
+
+/// Index of the item `from_end` items before the last one
+fn frame_index(from_end: usize) -> BlockInputBuilder {
+    let length = length_of_list(results_field());
+    BlockInputBuilder::stack(if from_end == 0 {
+        length
+    } else {
+        sub(
+            BlockInputBuilder::stack(length),
+            BlockInputBuilder::value(BlockInputValue::Number {
+                value: (from_end as f64).into(),
+            }),
+        )
+    })
+}
+
+/// Blocks that remove a frame of `size` results
+fn pop_results(size: usize) -> StackBuilder {
+    let mut stack = StackBuilder::with_capacity(size);
+    for _ in 0..size {
+        stack = stack.next(delete_in_list(
+            results_field(),
+            BlockInputBuilder::stack(length_of_list(results_field())),
+        ));
+    }
+    stack
+}
+
+/// Stack in the C-block's input `name`, added if there's none
+fn substack<'a>(block: &'a mut BlockNormalBuilder, name: &str) -> &'a mut StackBuilder {
+    let input = block
+        .inputs_mut()
+        .entry(name.to_owned())
+        .or_insert_with(|| BlockInputBuilder::stack(StackBuilder::new()));
+    if !matches!(input.values.first(), Some(Some(StackOrValue::Stack(_)))) {
+        *input = BlockInputBuilder::stack(StackBuilder::new());
+    }
+    match input.values.first_mut() {
+        Some(Some(StackOrValue::Stack(stack))) => stack,
+        _ => unreachable!("input was just replaced with a stack"),
+    }
+}
+
+fn is_hat(block: &BlockBuilder) -> bool {
+    match block {
+        BlockBuilder::Normal(n) => n
+            .opcode()
+            .info()
+            .map(|info| info.shape.is_hat())
+            .unwrap_or(false),
+        BlockBuilder::CustomBlock(_) => true,
+        BlockBuilder::CustomBlockCall(_) | BlockBuilder::VarList(_) => false,
+    }
+}
+
+fn substacks(block: &mut BlockBuilder) -> Vec<&mut StackBuilder> {
+    let BlockBuilder::Normal(n) = block else {
+        return vec![];
+    };
+    n.inputs_mut()
+        .iter_mut()
+        .filter(|(name, _)| name.starts_with("SUBSTACK"))
+        .flat_map(|(_, input)| &mut input.values)
+        .filter_map(|value| match value {
+            Some(StackOrValue::Stack(stack)) => Some(stack),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Inputs that aren't `SUBSTACK`, in the order they are on the block
+fn expression_inputs(block: &mut BlockBuilder) -> Vec<&mut BlockInputBuilder> {
+    match block {
+        BlockBuilder::Normal(n) => {
+            let order: Vec<&str> = n
+                .opcode()
+                .info()
+                .map(|info| info.inputs.iter().map(|input| input.name).collect())
+                .unwrap_or_default();
+            let mut inputs: Vec<(&String, &mut BlockInputBuilder)> = n
+                .inputs_mut()
+                .iter_mut()
+                .filter(|(name, _)| !name.starts_with("SUBSTACK"))
+                .collect();
+            inputs.sort_by_key(|(name, _)| {
+                (
+                    order
+                        .iter()
+                        .position(|input| *input == name.as_str())
+                        .unwrap_or(order.len()),
+                    name.to_string(),
+                )
+            });
+            inputs.into_iter().map(|(_, input)| input).collect()
+        }
+        BlockBuilder::CustomBlockCall(c) => c.args.iter_mut().map(|(_, input)| input).collect(),
+        BlockBuilder::CustomBlock(_) | BlockBuilder::VarList(_) => vec![],
+    }
+}
@@ -0,0 +1,385 @@
+//! Monitors on the stage
+//!
+//! Variables and lists are referred to by name and resolved to their ids in [`ProjectBuilder::build`](crate::project::ProjectBuilder::build).
+//! Monitors of things that don't exist are left out, [`Validator::validate_project`](crate::validate::Validator::validate_project) finds them.
+
+use sb_sbity::{monitor::Monitor, target::SpriteOrStage};
+use serde_json::json;
+
+use crate::{opcode::StandardOpCode, project::ProjectBuilder, uid::Uid, validate::ValidationError};
+
+/// What the monitor is showing
+#[derive(Debug, Clone, PartialEq)]
+pub enum MonitorOf {
+    Variable(String),
+    List(String),
+    Reporter(MonitorReporter),
+}
+
+/// Reporters that has a checkbox in the editor's palette
+///
+/// Reporters marked as sprite-specific need [`MonitorBuilder::sprite`]
+/// and the rest ignores it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MonitorReporter {
+    /// Sprite-specific
+    XPosition,
+    /// Sprite-specific
+    YPosition,
+    /// Sprite-specific
+    Direction,
+    /// Sprite-specific
+    ///
+    /// Accepts:
+    ///  - `"number"`
+    ///  - `"name"`
+    CostumeNumberName(String),
+    /// Sprite-specific
+    Size,
+    /// Sprite-specific, or the stage's volume when [`MonitorBuilder::sprite`] is [`None`]
+    Volume,
+    /// Accepts:
+    ///  - `"number"`
+    ///  - `"name"`
+    BackdropNumberName(String),
+    Answer,
+    Loudness,
+    Timer,
+    /// Accepts:
+    ///  - `"YEAR"`
+    ///  - `"MONTH"`
+    ///  - `"DATE"`
+    ///  - `"DAYOFWEEK"`
+    ///  - `"HOUR"`
+    ///  - `"MINUTE"`
+    ///  - `"SECOND"`
+    Current(String),
+    Username,
+}
+
+impl MonitorReporter {
+    pub fn opcode(&self) -> StandardOpCode {
+        use StandardOpCode::*;
+        match self {
+            MonitorReporter::XPosition => motion_xposition,
+            MonitorReporter::YPosition => motion_yposition,
+            MonitorReporter::Direction => motion_direction,
+            MonitorReporter::CostumeNumberName(_) => looks_costumenumbername,
+            MonitorReporter::Size => looks_size,
+            MonitorReporter::Volume => sound_volume,
+            MonitorReporter::BackdropNumberName(_) => looks_backdropnumbername,
+            MonitorReporter::Answer => sensing_answer,
+            MonitorReporter::Loudness => sensing_loudness,
+            MonitorReporter::Timer => sensing_timer,
+            MonitorReporter::Current(_) => sensing_current,
+            MonitorReporter::Username => sensing_username,
+        }
+    }
+
+    /// Monitor's id is prefixed with the target's id
+    pub fn is_target_specific(&self) -> bool {
+        matches!(
+            self,
+            MonitorReporter::XPosition
+                | MonitorReporter::YPosition
+                | MonitorReporter::Direction
+                | MonitorReporter::CostumeNumberName(_)
+                | MonitorReporter::Size
+                | MonitorReporter::Volume
+        )
+    }
+
+    /// Monitor's id without the target id prefix.
+    /// The editor names it after the palette block's id followed by `_` and the value of each field.
+    fn id(&self) -> String {
+        match self {
+            MonitorReporter::XPosition => "xposition".to_owned(),
+            MonitorReporter::YPosition => "yposition".to_owned(),
+            MonitorReporter::Direction => "direction".to_owned(),
+            MonitorReporter::CostumeNumberName(number_name) => {
+                format!("costumenumbername_{number_name}")
+            }
+            MonitorReporter::Size => "size".to_owned(),
+            MonitorReporter::Volume => "volume".to_owned(),
+            MonitorReporter::BackdropNumberName(number_name) => {
+                format!("backdropnumbername_{number_name}")
+            }
+            MonitorReporter::Answer => "answer".to_owned(),
+            MonitorReporter::Loudness => "loudness".to_owned(),
+            MonitorReporter::Timer => "timer".to_owned(),
+            MonitorReporter::Current(current) => format!("current_{}", current.to_lowercase()),
+            MonitorReporter::Username => "username".to_owned(),
+        }
+    }
+
+    fn params(&self) -> serde_json::Value {
+        match self {
+            MonitorReporter::CostumeNumberName(number_name)
+            | MonitorReporter::BackdropNumberName(number_name) => {
+                json!({ "NUMBER_NAME": number_name })
+            }
+            MonitorReporter::Current(current) => json!({ "CURRENTMENU": current }),
+            _ => json!({}),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MonitorMode {
+    /// Name and value
+    Normal,
+    /// Only value
+    Large,
+    /// Name, value and a slider to change the variable
+    Slider { min: f64, max: f64, discrete: bool },
+}
+
+/// Monitor for a variable, a list or a reporter
+///
+/// Monitors without a position are put in a column at the top left of the stage.
+#[rustfmt::skip]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorBuilder {
+    pub of:      MonitorOf,
+    /// Name of the sprite that has the variable or list or that the reporter is about, [`None`] for the stage.
+    pub sprite:  Option<String>,
+    /// Ignored for lists, sliders are only for variables.
+    pub mode:    MonitorMode,
+    pub x:       Option<f64>,
+    pub y:       Option<f64>,
+    /// Only for lists, 0 is the default size.
+    pub width:   f64,
+    /// Only for lists, 0 is the default size.
+    pub height:  f64,
+    pub visible: bool,
+}
+
+impl MonitorBuilder {
+    pub fn new(of: MonitorOf) -> MonitorBuilder {
+        MonitorBuilder {
+            of,
+            sprite: None,
+            mode: MonitorMode::Normal,
+            x: None,
+            y: None,
+            width: 0.,
+            height: 0.,
+            visible: true,
+        }
+    }
+
+    pub fn variable<S: Into<String>>(name: S) -> MonitorBuilder {
+        MonitorBuilder::new(MonitorOf::Variable(name.into()))
+    }
+
+    pub fn list<S: Into<String>>(name: S) -> MonitorBuilder {
+        MonitorBuilder::new(MonitorOf::List(name.into()))
+    }
+
+    pub fn reporter(reporter: MonitorReporter) -> MonitorBuilder {
+        MonitorBuilder::new(MonitorOf::Reporter(reporter))
+    }
+
+    pub fn set_sprite(&mut self, sprite: Option<String>) -> &mut Self {
+        self.sprite = sprite;
+        self
+    }
+
+    pub fn set_mode(&mut self, mode: MonitorMode) -> &mut Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn set_slider(&mut self, min: f64, max: f64, discrete: bool) -> &mut Self {
+        self.mode = MonitorMode::Slider { min, max, discrete };
+        self
+    }
+
+    pub fn set_pos(&mut self, x: Option<f64>, y: Option<f64>) -> &mut Self {
+        self.x = x;
+        self.y = y;
+        self
+    }
+
+    pub fn set_size(&mut self, width: f64, height: f64) -> &mut Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn set_visible(&mut self, visible: bool) -> &mut Self {
+        self.visible = visible;
+        self
+    }
+
+    /// Rough height on the stage, for putting monitors without a position below each other.
+    fn stage_height(&self) -> f64 {
+        match (&self.of, &self.mode) {
+            (MonitorOf::List(_), _) if self.height > 0. => self.height,
+            (MonitorOf::List(_), _) => 200.,
+            (MonitorOf::Variable(_), MonitorMode::Slider { .. }) => 45.,
+            (_, _) => 27.,
+        }
+    }
+
+    /// `next_y` is where the next monitor without a position goes.
+    ///
+    /// [`None`] when the sprite, variable or list doesn't exist or a sprite-specific reporter doesn't have a sprite,
+    /// [`Validator::validate_project`](crate::validate::Validator::validate_project) reports those.
+    pub(crate) fn build(self, targets: &[SpriteOrStage], next_y: &mut f64) -> Option<Monitor> {
+        let height = self.stage_height();
+        let MonitorBuilder {
+            of,
+            sprite,
+            mode,
+            x,
+            y,
+            width,
+            height: list_height,
+            visible,
+        } = self;
+        let target = targets.iter().find_map(|target| match (target, &sprite) {
+            (SpriteOrStage::Stage(stage), None) => Some(&stage.target),
+            (SpriteOrStage::Sprite(s), Some(name)) if &s.target.name == name => Some(&s.target),
+            _ => None,
+        })?;
+        let (x, y) = match (x, y) {
+            (Some(x), Some(y)) => (x, y),
+            (x, y) => {
+                let y = y.unwrap_or(*next_y);
+                *next_y = y + height + 5.;
+                (x.unwrap_or(5.), y)
+            }
+        };
+
+        // The editor always writes the slider range, even for monitors that can't be sliders
+        let (variable_mode, slider_min, slider_max, discrete) = match &mode {
+            MonitorMode::Normal => ("default", 0., 100., true),
+            MonitorMode::Large => ("large", 0., 100., true),
+            MonitorMode::Slider { min, max, discrete } => ("slider", *min, *max, *discrete),
+        };
+        let monitor = match of {
+            MonitorOf::Variable(name) => {
+                let (id, var) = target
+                    .variables
+                    .0
+                    .iter()
+                    .find(|(_, var)| var.name == name)?;
+                json!({
+                    "id": id,
+                    "mode": variable_mode,
+                    "opcode": "data_variable",
+                    "params": { "VARIABLE": name },
+                    "spriteName": sprite,
+                    "value": var.value,
+                    "width": 0,
+                    "height": 0,
+                    "x": x,
+                    "y": y,
+                    "visible": visible,
+                    "sliderMin": slider_min,
+                    "sliderMax": slider_max,
+                    "isDiscrete": discrete,
+                })
+            }
+            MonitorOf::List(name) => {
+                let (id, list) = target.lists.0.iter().find(|(_, list)| list.name == name)?;
+                json!({
+                    "id": id,
+                    "mode": "list",
+                    "opcode": "data_listcontents",
+                    "params": { "LIST": name },
+                    "spriteName": sprite,
+                    "value": list.values,
+                    "width": width,
+                    "height": list_height,
+                    "x": x,
+                    "y": y,
+                    "visible": visible,
+                    "sliderMin": 0,
+                    "sliderMax": 100,
+                    "isDiscrete": true,
+                })
+            }
+            MonitorOf::Reporter(reporter) => {
+                let (id, sprite_name) = if reporter.is_target_specific() {
+                    if sprite.is_none() && !matches!(reporter, MonitorReporter::Volume) {
+                        return None;
+                    }
+                    // The editor replaces everything before the first `_` with the target's actual id when loading
+                    let target_id = Uid::generate().into_inner().replace('_', "");
+                    (format!("{target_id}_{}", reporter.id()), sprite.as_deref())
+                } else {
+                    (reporter.id(), None)
+                };
+                let mode = match mode {
+                    MonitorMode::Large => "large",
+                    _ => "default",
+                };
+                json!({
+                    "id": id,
+                    "mode": mode,
+                    "opcode": reporter.opcode().to_string(),
+                    "params": reporter.params(),
+                    "spriteName": sprite_name,
+                    "value": 0,
+                    "width": 0,
+                    "height": 0,
+                    "x": x,
+                    "y": y,
+                    "visible": visible,
+                    "sliderMin": 0,
+                    "sliderMax": 100,
+                    "isDiscrete": true,
+                })
+            }
+        };
+        Some(
+            serde_json::from_value(monitor)
+                .expect("monitor json is written in the sb3 format that Monitor deserializes"),
+        )
+    }
+
+    /// Why this monitor can't be built in `project`
+    pub(crate) fn validate(&self, project: &ProjectBuilder) -> Option<ValidationError> {
+        let target = match &self.sprite {
+            None => &project.stage_builder.target,
+            Some(name) => match project
+                .sprite_builders
+                .iter()
+                .find(|sprite| &sprite.target.name == name)
+            {
+                Some(sprite) => &sprite.target,
+                None => {
+                    return Some(ValidationError::UnknownSprite {
+                        sprite: name.clone(),
+                    })
+                }
+            },
+        };
+        match &self.of {
+            MonitorOf::Variable(name) if !target.variables.contains_key(name) => {
+                Some(ValidationError::UnknownVariable {
+                    sprite: self.sprite.clone(),
+                    name: name.clone(),
+                })
+            }
+            MonitorOf::List(name) if !target.lists.contains_key(name) => {
+                Some(ValidationError::UnknownList {
+                    sprite: self.sprite.clone(),
+                    name: name.clone(),
+                })
+            }
+            MonitorOf::Reporter(reporter)
+                if reporter.is_target_specific()
+                    && self.sprite.is_none()
+                    && !matches!(reporter, MonitorReporter::Volume) =>
+            {
+                Some(ValidationError::MonitorWithoutSprite {
+                    opcode: reporter.opcode().to_string(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
@@ -8,10 +8,63 @@
 
 #![allow(unused)]
 
-use sb_sbity::value::OpCode;
+use std::str::FromStr;
 
+/// Declare an opcode enum that can be parsed back from its opcode string
+macro_rules! opcode_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($variant:ident,)*
+        }
+    ) => {
+        $(#[$meta])*
+        pub enum $name {
+            $($variant,)*
+        }
+
+        impl $name {
+            pub const ALL: &'static [$name] = &[$($name::$variant,)*];
+
+            /// The opcode string, like `"motion_movesteps"`
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($name::$variant => stringify!($variant),)*
+                }
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseOpCodeError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $(stringify!($variant) => Ok($name::$variant),)*
+                    _ => Err(ParseOpCodeError {
+                        opcode: s.to_owned(),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOpCodeError {
+    pub opcode: String,
+}
+
+impl std::error::Error for ParseOpCodeError {}
+
+impl std::fmt::Display for ParseOpCodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown opcode `{}`", self.opcode)
+    }
+}
+
+opcode_enum! {
 #[allow(non_camel_case_types)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum StandardOpCode {
     // i love regex
     control_forever,
@@ -186,9 +239,37 @@ pub enum StandardOpCode {
     sound_setvolumeto,
     sound_volume,
 }
+}
+
+impl StandardOpCode {
+    /// Blocks that doesn't appear in the editor's palette but still run in the VM.
+    pub const HIDDEN: &'static [StandardOpCode] = &[
+        StandardOpCode::control_while,
+        StandardOpCode::control_for_each,
+        StandardOpCode::control_get_counter,
+        StandardOpCode::control_incr_counter,
+        StandardOpCode::control_clear_counter,
+        StandardOpCode::control_all_at_once,
+        StandardOpCode::looks_hideallsprites,
+        StandardOpCode::looks_changestretchby,
+        StandardOpCode::looks_setstretchto,
+        StandardOpCode::motion_scroll_right,
+        StandardOpCode::motion_scroll_up,
+        StandardOpCode::motion_align_scene,
+        StandardOpCode::motion_xscroll,
+        StandardOpCode::motion_yscroll,
+        StandardOpCode::sensing_loud,
+        StandardOpCode::sensing_userid,
+    ];
+
+    pub fn is_hidden(&self) -> bool {
+        StandardOpCode::HIDDEN.contains(self)
+    }
+}
 
+opcode_enum! {
 #[allow(non_camel_case_types)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PenExtensionOpCode {
     pen_clear,
     pen_stamp,
@@ -204,19 +285,20 @@ pub enum PenExtensionOpCode {
     pen_setPenHueToNumber,
     pen_changePenHueBy,
 }
+}
 
 macro_rules! impl_things {
     ($($ty:ty)*) => {
         $(
-            impl From<$ty> for OpCode {
-                fn from(t: $ty) -> OpCode {
+            impl From<$ty> for String {
+                fn from(t: $ty) -> String {
                     t.to_string()
                 }
             }
 
             impl std::fmt::Display for $ty {
                 fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                    write!(f, "{:?}", self)
+                    f.write_str(self.as_str())
                 }
             }
         )*
@@ -224,3 +306,102 @@ macro_rules! impl_things {
 }
 
 impl_things! { StandardOpCode PenExtensionOpCode }
+
+/// Opcode of a block
+///
+/// Parsing never fails, opcodes that isn't [`StandardOpCode`] or [`PenExtensionOpCode`]
+/// are kept as [`OpCode::Extension`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OpCode {
+    Standard(StandardOpCode),
+    Pen(PenExtensionOpCode),
+    /// Opcode of other extensions or opcode this crate doesn't know about
+    Extension(String),
+}
+
+impl OpCode {
+    pub fn parse(opcode: &str) -> OpCode {
+        if let Ok(opcode) = opcode.parse() {
+            return OpCode::Standard(opcode);
+        }
+        if let Ok(opcode) = opcode.parse() {
+            return OpCode::Pen(opcode);
+        }
+        OpCode::Extension(opcode.to_owned())
+    }
+
+    /// The opcode string, like `"motion_movesteps"`
+    pub fn as_str(&self) -> &str {
+        match self {
+            OpCode::Standard(opcode) => opcode.as_str(),
+            OpCode::Pen(opcode) => opcode.as_str(),
+            OpCode::Extension(opcode) => opcode,
+        }
+    }
+
+    pub fn info(&self) -> Option<&'static crate::registry::OpCodeInfo> {
+        crate::registry::lookup(self.as_str())
+    }
+}
+
+impl Default for OpCode {
+    fn default() -> Self {
+        OpCode::Extension(String::new())
+    }
+}
+
+impl FromStr for OpCode {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(OpCode::parse(s))
+    }
+}
+
+impl std::fmt::Display for OpCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<StandardOpCode> for OpCode {
+    fn from(opcode: StandardOpCode) -> Self {
+        OpCode::Standard(opcode)
+    }
+}
+
+impl From<PenExtensionOpCode> for OpCode {
+    fn from(opcode: PenExtensionOpCode) -> Self {
+        OpCode::Pen(opcode)
+    }
+}
+
+impl From<&str> for OpCode {
+    fn from(opcode: &str) -> Self {
+        OpCode::parse(opcode)
+    }
+}
+
+impl From<String> for OpCode {
+    fn from(opcode: String) -> Self {
+        OpCode::parse(&opcode)
+    }
+}
+
+impl From<OpCode> for String {
+    fn from(opcode: OpCode) -> Self {
+        opcode.to_string()
+    }
+}
+
+impl PartialEq<str> for OpCode {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for OpCode {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
@@ -1,9 +1,14 @@
 use std::collections::HashMap;
 
 use crate::{
+    custom_block::{CustomBlockHandle, CustomBlockTy, SharedCustomBlock},
+    extension::{ExtensionDef, ExtensionInfo},
+    monitor::MonitorBuilder,
     resource::Resource,
+    stack::StackBuilder,
     target::{SpriteBuilder, StageBuilder},
     uid::Uid,
+    validate::{Severity, ValidationError, Validator},
 };
 use sb_sbity::{
     monitor::Monitor,
@@ -16,7 +21,10 @@ use sb_sbity::{
 pub struct ProjectBuilder {
     pub stage_builder:   StageBuilder,
     pub sprite_builders: Vec<SpriteBuilder>,
-    pub monitors:        Vec<Monitor>,
+    pub monitors:        Vec<MonitorBuilder>,
+    pub extensions:      Vec<ExtensionInfo>,
+    /// Custom blocks that any target can call
+    pub custom_blocks:   Vec<SharedCustomBlock>,
     pub meta:            Meta,
 }
 
@@ -30,16 +38,98 @@ impl ProjectBuilder {
         self.sprite_builders.push(sprite_builder);
         self
     }
+
+    /// Give every sprite a layer order from 1 to the amount of sprites.
+    ///
+    /// Sprites keep the order of their [`TargetBuilder::layer_order`](crate::target::TargetBuilder::layer_order).
+    /// Sprites without one are put as if their layer order is their position in [`ProjectBuilder::sprite_builders`] counting from 1.
+    /// Done automatically in [`ProjectBuilder::build`].
+    pub fn assign_layer_orders(&mut self) -> &mut Self {
+        let mut order: Vec<(u64, usize)> = self
+            .sprite_builders
+            .iter()
+            .enumerate()
+            .map(|(i, sprite)| (sprite.target.layer_order.unwrap_or(i as u64 + 1), i))
+            .collect();
+        order.sort();
+        for (layer, (_, i)) in order.into_iter().enumerate() {
+            self.sprite_builders[i].target.layer_order = Some(layer as u64 + 1);
+        }
+        self.stage_builder.target.layer_order = Some(0);
+        self
+    }
+
+    /// Put the sprite in front of every other sprite. Does nothing if there's no sprite with this name.
+    pub fn move_sprite_to_front(&mut self, name: &str) -> &mut Self {
+        self.move_sprite_to_layer(name, u64::MAX)
+    }
+
+    /// Put the sprite behind every other sprite. Does nothing if there's no sprite with this name.
+    pub fn move_sprite_to_back(&mut self, name: &str) -> &mut Self {
+        self.move_sprite_to_layer(name, 0)
+    }
+
+    fn move_sprite_to_layer(&mut self, name: &str, layer: u64) -> &mut Self {
+        self.assign_layer_orders();
+        if let Some(sprite) = self
+            .sprite_builders
+            .iter_mut()
+            .find(|sprite| sprite.target.name == name)
+        {
+            sprite.target.layer_order = Some(layer);
+        }
+        self.assign_layer_orders()
+    }
+
+    pub fn add_monitor(&mut self, monitor_builder: MonitorBuilder) -> &mut Self {
+        self.monitors.push(monitor_builder);
+        self
+    }
+
+    /// Add a custom block to the project's library.
+    ///
+    /// When building, it's defined in every sprite and stage that calls it
+    /// and isn't emitted in the ones that don't.
+    pub fn define_custom_block(
+        &mut self,
+        signature: CustomBlockTy,
+        body: StackBuilder,
+    ) -> CustomBlockHandle {
+        let handle = CustomBlockHandle::new(signature.clone());
+        self.custom_blocks
+            .push(SharedCustomBlock { signature, body });
+        handle
+    }
+
+    /// Adding the same extension twice does nothing.
+    pub fn add_extension<E: ExtensionDef>(&mut self, extension: &E) -> &mut Self {
+        let extension = extension.info();
+        if !self.extensions.iter().any(|added| added.id == extension.id) {
+            self.extensions.push(extension);
+        }
+        self
+    }
 }
 
 impl ProjectBuilder {
-    pub fn build(self, res_buf: &mut Vec<Resource>) -> Project {
+    /// Build into a [`Project`].
+    ///
+    /// [`Project`] doesn't have `extensionURLs` so extensions with a [`url`](ExtensionDef::url) won't load,
+    /// use [`ProjectBuilder::build_json`] when the project has them.
+    pub fn build(mut self, res_buf: &mut Vec<Resource>) -> Project {
+        self.assign_layer_orders();
         let ProjectBuilder {
-            stage_builder,
-            sprite_builders,
+            mut stage_builder,
+            mut sprite_builders,
             monitors,
+            extensions,
+            custom_blocks,
             meta,
         } = self;
+        stage_builder.target.add_used_custom_blocks(&custom_blocks);
+        for sprite_builder in &mut sprite_builders {
+            sprite_builder.target.add_used_custom_blocks(&custom_blocks);
+        }
 
         let all_broadcasts: HashMap<String, Uid> = stage_builder
             .target
@@ -59,15 +149,68 @@ impl ProjectBuilder {
                 &all_broadcasts,
             ))
         }));
+        let mut next_monitor_y = 5.;
+        let monitors: Vec<Monitor> = monitors
+            .into_iter()
+            .filter_map(|monitor_builder| monitor_builder.build(&targets, &mut next_monitor_y))
+            .collect();
         Project {
             meta,
-            extensions: serde_json::value::Value::Array(vec![]),
+            extensions: serde_json::value::Value::Array(
+                extensions
+                    .iter()
+                    .map(|extension| extension.id.into())
+                    .collect(),
+            ),
             monitors,
             targets,
         }
     }
 }
 
+impl ProjectBuilder {
+    /// Run `validator` over the project and only build it when there are no errors.
+    ///
+    /// Warnings are put in `warnings` and don't stop the build.
+    pub fn build_validated(
+        self,
+        res_buf: &mut Vec<Resource>,
+        validator: &Validator,
+        warnings: &mut Vec<ValidationError>,
+    ) -> Result<Project, Vec<ValidationError>> {
+        let (found_warnings, errors): (Vec<_>, Vec<_>) = validator
+            .validate_project(&self)
+            .into_iter()
+            .partition(|error| error.severity() == Severity::Warning);
+        warnings.extend(found_warnings);
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(self.build(res_buf))
+    }
+}
+
+impl ProjectBuilder {
+    /// Build into project.json.
+    ///
+    /// Unlike [`Project`], this also has `extensionURLs`
+    /// which the editor needs to load extensions that doesn't come with it.
+    pub fn build_json(self, res_buf: &mut Vec<Resource>) -> serde_json::Value {
+        let extension_urls: serde_json::Map<String, serde_json::Value> = self
+            .extensions
+            .iter()
+            .filter_map(|extension| Some((extension.id.to_owned(), extension.url?.into())))
+            .collect();
+        let project = self.build(res_buf);
+        let mut json = serde_json::to_value(project)
+            .expect("Project only has string map keys so it always serializes to JSON");
+        if !extension_urls.is_empty() {
+            json["extensionURLs"] = serde_json::Value::Object(extension_urls);
+        }
+        json
+    }
+}
+
 impl Default for ProjectBuilder {
     #[rustfmt::skip]
     fn default() -> Self {
@@ -75,6 +218,8 @@ impl Default for ProjectBuilder {
             stage_builder:   StageBuilder::default(),
             sprite_builders: Vec::default(),
             monitors:        Vec::default(),
+            extensions:      Vec::default(),
+            custom_blocks:   Vec::default(),
             meta: Meta {
                 semver: "3.0.0".to_owned(),
                 vm:     "0.2.0-prerelease.20220222132735".to_owned(),
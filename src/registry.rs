@@ -0,0 +1,614 @@
+//! What each opcode looks like
+//!
+//! One table describing the shape, label, inputs and fields of every block that this crate knows about.
+//! Layout, validation and anything else that needs to know about a block should look it up here
+//! instead of guessing from the opcode string.
+//!
+//! Labels are the English text of the block with the inputs and fields written as `[NAME]`.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::opcode::{PenExtensionOpCode, StandardOpCode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockShape {
+    /// Start of a script, nothing can be above it.
+    Hat,
+    /// Normal block with blocks above and below it.
+    Stack,
+    /// Nothing can be below it.
+    Cap,
+    /// Has one `SUBSTACK`.
+    CBlock,
+    /// Has one `SUBSTACK` and nothing can be below it. Like `forever`.
+    CapCBlock,
+    /// Has `SUBSTACK` and `SUBSTACK2`.
+    EBlock,
+    /// Round reporter.
+    Reporter,
+    /// Pointy reporter.
+    Boolean,
+}
+
+impl BlockShape {
+    pub fn is_hat(&self) -> bool {
+        matches!(self, BlockShape::Hat)
+    }
+
+    /// Can be put in an input instead of a stack
+    pub fn is_reporter(&self) -> bool {
+        matches!(self, BlockShape::Reporter | BlockShape::Boolean)
+    }
+
+    /// Can have a block below it
+    pub fn has_next(&self) -> bool {
+        matches!(
+            self,
+            BlockShape::Hat | BlockShape::Stack | BlockShape::CBlock | BlockShape::EBlock
+        )
+    }
+
+    /// Can have a block above it
+    pub fn has_previous(&self) -> bool {
+        !self.is_hat() && !self.is_reporter()
+    }
+}
+
+/// What is expected to be in an input when nothing is put in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Number,
+    PositiveNumber,
+    WholeNumber,
+    Integer,
+    Angle,
+    Color,
+    Text,
+    Broadcast,
+    /// Empty pointy slot
+    Boolean,
+    /// Blocks inside C-block or E-block
+    SubStack,
+    /// A shadow menu block with this opcode
+    Menu(&'static str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldMenu {
+    /// Fixed options
+    Options(&'static [&'static str]),
+    /// Name of a sprite or one of the special options
+    Sprite(&'static [&'static str]),
+    /// Name of a costume in the sprite
+    Costume,
+    /// Name of a backdrop or one of the special options
+    Backdrop(&'static [&'static str]),
+    /// Name of a sound in the sprite
+    Sound,
+    Variable,
+    List,
+    Broadcast,
+    /// Anything
+    Text,
+}
+
+impl FieldMenu {
+    /// Options that are always in the menu
+    pub fn options(&self) -> &'static [&'static str] {
+        match self {
+            FieldMenu::Options(options)
+            | FieldMenu::Sprite(options)
+            | FieldMenu::Backdrop(options) => options,
+            _ => &[],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputInfo {
+    pub name: &'static str,
+    pub kind: InputKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldInfo {
+    pub name: &'static str,
+    pub menu: FieldMenu,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelPart {
+    Text(&'static str),
+    /// Name of an input or a field
+    Slot(&'static str),
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpCodeInfo {
+    pub opcode:    &'static str,
+    pub shape:     BlockShape,
+    pub label:     &'static str,
+    /// In the order they appear on the block
+    pub inputs:    &'static [InputInfo],
+    /// In the order they appear on the block
+    pub fields:    &'static [FieldInfo],
+    /// Extension id, [`None`] for blocks that's in Scratch by default
+    pub extension: Option<&'static str>,
+}
+
+impl OpCodeInfo {
+    pub fn input(&self, name: &str) -> Option<&'static InputInfo> {
+        self.inputs.iter().find(|input| input.name == name)
+    }
+
+    pub fn field(&self, name: &str) -> Option<&'static FieldInfo> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// See [`palette_default`]
+    pub fn palette_default(&self, slot: &str) -> Option<&'static str> {
+        palette_default(self.opcode, slot)
+    }
+
+    /// Label split into text and `[NAME]` slots, in order
+    pub fn label_parts(&self) -> impl Iterator<Item = LabelPart> {
+        self.label
+            .split(['[', ']'])
+            .enumerate()
+            .filter(|(_, part)| !part.is_empty())
+            .map(|(i, part)| {
+                if i % 2 == 0 {
+                    LabelPart::Text(part)
+                } else {
+                    LabelPart::Slot(part)
+                }
+            })
+    }
+
+    /// Inputs and fields are decided by the block's mutation. Like `procedures_call`.
+    pub fn is_dynamic(&self) -> bool {
+        matches!(
+            self.opcode,
+            "procedures_call" | "procedures_prototype" | "procedures_declaration"
+        )
+    }
+}
+
+/// Find the metadata of an opcode
+pub fn lookup(opcode: &str) -> Option<&'static OpCodeInfo> {
+    static INDEX: OnceLock<HashMap<&'static str, &'static OpCodeInfo>> = OnceLock::new();
+    INDEX
+        .get_or_init(|| REGISTRY.iter().map(|info| (info.opcode, info)).collect())
+        .get(opcode)
+        .copied()
+}
+
+/// Every block this crate knows about
+pub fn all() -> &'static [OpCodeInfo] {
+    REGISTRY
+}
+
+/// Every `(opcode, slot, value)` that [`palette_default`] knows about
+pub fn palette_defaults() -> &'static [(&'static str, &'static str, &'static str)] {
+    PALETTE_DEFAULTS
+}
+
+/// Value an input has when the block is dragged out of the palette.
+///
+/// For menu blocks `slot` is the name of the field and this is the option the menu starts on.
+/// [`None`] for inputs that are empty in the palette and menus that start on their first option.
+pub fn palette_default(opcode: &str, slot: &str) -> Option<&'static str> {
+    PALETTE_DEFAULTS
+        .iter()
+        .find(|(o, s, _)| *o == opcode && *s == slot)
+        .map(|(_, _, value)| *value)
+}
+
+impl StandardOpCode {
+    /// [`None`] for opcodes that isn't a real block. Like [`StandardOpCode::extension_pen_down`]
+    pub fn info(&self) -> Option<&'static OpCodeInfo> {
+        lookup(&self.to_string())
+    }
+}
+
+impl PenExtensionOpCode {
+    pub fn info(&self) -> Option<&'static OpCodeInfo> {
+        lookup(&self.to_string())
+    }
+}
+
+const fn op(
+    opcode: &'static str,
+    shape: BlockShape,
+    label: &'static str,
+    inputs: &'static [InputInfo],
+    fields: &'static [FieldInfo],
+) -> OpCodeInfo {
+    OpCodeInfo {
+        opcode,
+        shape,
+        label,
+        inputs,
+        fields,
+        extension: None,
+    }
+}
+
+const fn ext(
+    extension: &'static str,
+    opcode: &'static str,
+    shape: BlockShape,
+    label: &'static str,
+    inputs: &'static [InputInfo],
+    fields: &'static [FieldInfo],
+) -> OpCodeInfo {
+    OpCodeInfo {
+        opcode,
+        shape,
+        label,
+        inputs,
+        fields,
+        extension: Some(extension),
+    }
+}
+
+macro_rules! i {
+    ($name:literal, $kind:expr) => {
+        InputInfo {
+            name: $name,
+            kind: $kind,
+        }
+    };
+}
+
+macro_rules! f {
+    ($name:literal, $menu:expr) => {
+        FieldInfo {
+            name: $name,
+            menu: $menu,
+        }
+    };
+}
+
+#[rustfmt::skip]
+const KEYS: &[&str] = &[
+    "space", "up arrow", "down arrow", "right arrow", "left arrow", "any",
+    "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m",
+    "n", "o", "p", "q", "r", "s", "t", "u", "v", "w", "x", "y", "z",
+    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9",
+];
+#[rustfmt::skip]
+const LOOKS_EFFECTS: &[&str] = &[
+    "COLOR", "FISHEYE", "WHIRL", "PIXELATE", "MOSAIC", "BRIGHTNESS", "GHOST",
+];
+const SOUND_EFFECTS: &[&str] = &["PITCH", "PAN"];
+const NUMBER_NAME: &[&str] = &["number", "name"];
+#[rustfmt::skip]
+const MATH_OPS: &[&str] = &[
+    "abs", "floor", "ceiling", "sqrt", "sin", "cos", "tan",
+    "asin", "acos", "atan", "ln", "log", "e ^", "10 ^",
+];
+#[rustfmt::skip]
+const CURRENT: &[&str] = &[
+    "YEAR", "MONTH", "DATE", "DAYOFWEEK", "HOUR", "MINUTE", "SECOND",
+];
+
+#[rustfmt::skip]
+static REGISTRY: &[OpCodeInfo] = {
+    use BlockShape as S;
+    use FieldMenu as F;
+    use InputKind as I;
+    &[
+        // Motion ==============================================================
+        op("motion_movesteps",        S::Stack,    "move [STEPS] steps",                    &[i!("STEPS", I::Number)], &[]),
+        op("motion_turnright",        S::Stack,    "turn right [DEGREES] degrees",          &[i!("DEGREES", I::Number)], &[]),
+        op("motion_turnleft",         S::Stack,    "turn left [DEGREES] degrees",           &[i!("DEGREES", I::Number)], &[]),
+        op("motion_goto",             S::Stack,    "go to [TO]",                            &[i!("TO", I::Menu("motion_goto_menu"))], &[]),
+        op("motion_goto_menu",        S::Reporter, "[TO]",                                  &[], &[f!("TO", F::Sprite(&["_random_", "_mouse_"]))]),
+        op("motion_gotoxy",           S::Stack,    "go to x: [X] y: [Y]",                   &[i!("X", I::Number), i!("Y", I::Number)], &[]),
+        op("motion_glideto",          S::Stack,    "glide [SECS] secs to [TO]",             &[i!("SECS", I::Number), i!("TO", I::Menu("motion_glideto_menu"))], &[]),
+        op("motion_glideto_menu",     S::Reporter, "[TO]",                                  &[], &[f!("TO", F::Sprite(&["_random_", "_mouse_"]))]),
+        op("motion_glidesecstoxy",    S::Stack,    "glide [SECS] secs to x: [X] y: [Y]",    &[i!("SECS", I::Number), i!("X", I::Number), i!("Y", I::Number)], &[]),
+        op("motion_pointindirection", S::Stack,    "point in direction [DIRECTION]",        &[i!("DIRECTION", I::Angle)], &[]),
+        op("motion_pointtowards",     S::Stack,    "point towards [TOWARDS]",               &[i!("TOWARDS", I::Menu("motion_pointtowards_menu"))], &[]),
+        op("motion_pointtowards_menu",S::Reporter, "[TOWARDS]",                             &[], &[f!("TOWARDS", F::Sprite(&["_mouse_", "_random_"]))]),
+        op("motion_changexby",        S::Stack,    "change x by [DX]",                      &[i!("DX", I::Number)], &[]),
+        op("motion_setx",             S::Stack,    "set x to [X]",                          &[i!("X", I::Number)], &[]),
+        op("motion_changeyby",        S::Stack,    "change y by [DY]",                      &[i!("DY", I::Number)], &[]),
+        op("motion_sety",             S::Stack,    "set y to [Y]",                          &[i!("Y", I::Number)], &[]),
+        op("motion_ifonedgebounce",   S::Stack,    "if on edge, bounce",                    &[], &[]),
+        op("motion_setrotationstyle", S::Stack,    "set rotation style [STYLE]",            &[], &[f!("STYLE", F::Options(&["left-right", "don't rotate", "all around"]))]),
+        op("motion_xposition",        S::Reporter, "x position",                            &[], &[]),
+        op("motion_yposition",        S::Reporter, "y position",                            &[], &[]),
+        op("motion_direction",        S::Reporter, "direction",                             &[], &[]),
+        op("motion_scroll_right",     S::Stack,    "scroll right [DISTANCE]",               &[i!("DISTANCE", I::Number)], &[]),
+        op("motion_scroll_up",        S::Stack,    "scroll up [DISTANCE]",                  &[i!("DISTANCE", I::Number)], &[]),
+        op("motion_align_scene",      S::Stack,    "align scene [ALIGNMENT]",               &[], &[f!("ALIGNMENT", F::Options(&["bottom-left", "bottom-right", "middle", "top-left", "top-right"]))]),
+        op("motion_xscroll",          S::Reporter, "x scroll",                              &[], &[]),
+        op("motion_yscroll",          S::Reporter, "y scroll",                              &[], &[]),
+
+        // Looks ===============================================================
+        op("looks_sayforsecs",              S::Stack,    "say [MESSAGE] for [SECS] seconds",       &[i!("MESSAGE", I::Text), i!("SECS", I::Number)], &[]),
+        op("looks_say",                     S::Stack,    "say [MESSAGE]",                          &[i!("MESSAGE", I::Text)], &[]),
+        op("looks_thinkforsecs",            S::Stack,    "think [MESSAGE] for [SECS] seconds",     &[i!("MESSAGE", I::Text), i!("SECS", I::Number)], &[]),
+        op("looks_think",                   S::Stack,    "think [MESSAGE]",                        &[i!("MESSAGE", I::Text)], &[]),
+        op("looks_switchcostumeto",         S::Stack,    "switch costume to [COSTUME]",            &[i!("COSTUME", I::Menu("looks_costume"))], &[]),
+        op("looks_costume",                 S::Reporter, "[COSTUME]",                              &[], &[f!("COSTUME", F::Costume)]),
+        op("looks_nextcostume",             S::Stack,    "next costume",                           &[], &[]),
+        op("looks_switchbackdropto",        S::Stack,    "switch backdrop to [BACKDROP]",          &[i!("BACKDROP", I::Menu("looks_backdrops"))], &[]),
+        op("looks_switchbackdroptoandwait", S::Stack,    "switch backdrop to [BACKDROP] and wait", &[i!("BACKDROP", I::Menu("looks_backdrops"))], &[]),
+        op("looks_backdrops",               S::Reporter, "[BACKDROP]",                             &[], &[f!("BACKDROP", F::Backdrop(&["next backdrop", "previous backdrop", "random backdrop"]))]),
+        op("looks_nextbackdrop",            S::Stack,    "next backdrop",                          &[], &[]),
+        op("looks_changesizeby",            S::Stack,    "change size by [CHANGE]",                &[i!("CHANGE", I::Number)], &[]),
+        op("looks_setsizeto",               S::Stack,    "set size to [SIZE] %",                   &[i!("SIZE", I::Number)], &[]),
+        op("looks_changeeffectby",          S::Stack,    "change [EFFECT] effect by [CHANGE]",     &[i!("CHANGE", I::Number)], &[f!("EFFECT", F::Options(LOOKS_EFFECTS))]),
+        op("looks_seteffectto",             S::Stack,    "set [EFFECT] effect to [VALUE]",         &[i!("VALUE", I::Number)], &[f!("EFFECT", F::Options(LOOKS_EFFECTS))]),
+        op("looks_cleargraphiceffects",     S::Stack,    "clear graphic effects",                  &[], &[]),
+        op("looks_show",                    S::Stack,    "show",                                   &[], &[]),
+        op("looks_hide",                    S::Stack,    "hide",                                   &[], &[]),
+        op("looks_hideallsprites",          S::Stack,    "hide all sprites",                       &[], &[]),
+        op("looks_gotofrontback",           S::Stack,    "go to [FRONT_BACK] layer",               &[], &[f!("FRONT_BACK", F::Options(&["front", "back"]))]),
+        op("looks_goforwardbackwardlayers", S::Stack,    "go [FORWARD_BACKWARD] [NUM] layers",     &[i!("NUM", I::Integer)], &[f!("FORWARD_BACKWARD", F::Options(&["forward", "backward"]))]),
+        op("looks_costumenumbername",       S::Reporter, "costume [NUMBER_NAME]",                  &[], &[f!("NUMBER_NAME", F::Options(NUMBER_NAME))]),
+        op("looks_backdropnumbername",      S::Reporter, "backdrop [NUMBER_NAME]",                 &[], &[f!("NUMBER_NAME", F::Options(NUMBER_NAME))]),
+        op("looks_size",                    S::Reporter, "size",                                   &[], &[]),
+        op("looks_changestretchby",         S::Stack,    "change stretch by [CHANGE]",             &[i!("CHANGE", I::Number)], &[]),
+        op("looks_setstretchto",            S::Stack,    "set stretch to [STRETCH] %",             &[i!("STRETCH", I::Number)], &[]),
+
+        // Sound ===============================================================
+        op("sound_playuntildone",  S::Stack,    "play sound [SOUND_MENU] until done", &[i!("SOUND_MENU", I::Menu("sound_sounds_menu"))], &[]),
+        op("sound_play",           S::Stack,    "start sound [SOUND_MENU]",           &[i!("SOUND_MENU", I::Menu("sound_sounds_menu"))], &[]),
+        op("sound_sounds_menu",    S::Reporter, "[SOUND_MENU]",                       &[], &[f!("SOUND_MENU", F::Sound)]),
+        op("sound_stopallsounds",  S::Stack,    "stop all sounds",                    &[], &[]),
+        op("sound_changeeffectby", S::Stack,    "change [EFFECT] effect by [VALUE]",  &[i!("VALUE", I::Number)], &[f!("EFFECT", F::Options(SOUND_EFFECTS))]),
+        op("sound_seteffectto",    S::Stack,    "set [EFFECT] effect to [VALUE]",     &[i!("VALUE", I::Number)], &[f!("EFFECT", F::Options(SOUND_EFFECTS))]),
+        op("sound_cleareffects",   S::Stack,    "clear sound effects",                &[], &[]),
+        op("sound_changevolumeby", S::Stack,    "change volume by [VOLUME]",          &[i!("VOLUME", I::Number)], &[]),
+        op("sound_setvolumeto",    S::Stack,    "set volume to [VOLUME] %",           &[i!("VOLUME", I::Number)], &[]),
+        op("sound_volume",         S::Reporter, "volume",                             &[], &[]),
+
+        // Event ===============================================================
+        op("event_whenflagclicked",        S::Hat,      "when flag clicked",                           &[], &[]),
+        op("event_whenkeypressed",         S::Hat,      "when [KEY_OPTION] key pressed",               &[], &[f!("KEY_OPTION", F::Options(KEYS))]),
+        op("event_whenthisspriteclicked",  S::Hat,      "when this sprite clicked",                    &[], &[]),
+        op("event_whenstageclicked",       S::Hat,      "when stage clicked",                          &[], &[]),
+        op("event_whenbackdropswitchesto", S::Hat,      "when backdrop switches to [BACKDROP]",        &[], &[f!("BACKDROP", F::Backdrop(&[]))]),
+        op("event_whengreaterthan",        S::Hat,      "when [WHENGREATERTHANMENU] > [VALUE]",        &[i!("VALUE", I::Number)], &[f!("WHENGREATERTHANMENU", F::Options(&["LOUDNESS", "TIMER"]))]),
+        op("event_whenbroadcastreceived",  S::Hat,      "when I receive [BROADCAST_OPTION]",           &[], &[f!("BROADCAST_OPTION", F::Broadcast)]),
+        op("event_whentouchingobject",     S::Hat,      "when this sprite touches [TOUCHINGOBJECTMENU]", &[i!("TOUCHINGOBJECTMENU", I::Menu("event_touchingobjectmenu"))], &[]),
+        op("event_touchingobjectmenu",     S::Reporter, "[TOUCHINGOBJECTMENU]",                        &[], &[f!("TOUCHINGOBJECTMENU", F::Sprite(&["_mouse_", "_edge_"]))]),
+        op("event_broadcast",              S::Stack,    "broadcast [BROADCAST_INPUT]",                 &[i!("BROADCAST_INPUT", I::Broadcast)], &[]),
+        op("event_broadcastandwait",       S::Stack,    "broadcast [BROADCAST_INPUT] and wait",        &[i!("BROADCAST_INPUT", I::Broadcast)], &[]),
+        op("event_broadcast_menu",         S::Reporter, "[BROADCAST_OPTION]",                          &[], &[f!("BROADCAST_OPTION", F::Broadcast)]),
+
+        // Control =============================================================
+        op("control_wait",                 S::Stack,     "wait [DURATION] seconds",        &[i!("DURATION", I::PositiveNumber)], &[]),
+        op("control_repeat",               S::CBlock,    "repeat [TIMES]",                 &[i!("TIMES", I::WholeNumber), i!("SUBSTACK", I::SubStack)], &[]),
+        op("control_forever",              S::CapCBlock, "forever",                        &[i!("SUBSTACK", I::SubStack)], &[]),
+        op("control_if",                   S::CBlock,    "if [CONDITION] then",            &[i!("CONDITION", I::Boolean), i!("SUBSTACK", I::SubStack)], &[]),
+        op("control_if_else",              S::EBlock,    "if [CONDITION] then else",       &[i!("CONDITION", I::Boolean), i!("SUBSTACK", I::SubStack), i!("SUBSTACK2", I::SubStack)], &[]),
+        op("control_wait_until",           S::Stack,     "wait until [CONDITION]",         &[i!("CONDITION", I::Boolean)], &[]),
+        op("control_repeat_until",         S::CBlock,    "repeat until [CONDITION]",       &[i!("CONDITION", I::Boolean), i!("SUBSTACK", I::SubStack)], &[]),
+        op("control_while",                S::CBlock,    "while [CONDITION]",              &[i!("CONDITION", I::Boolean), i!("SUBSTACK", I::SubStack)], &[]),
+        op("control_for_each",             S::CBlock,    "for each [VARIABLE] in [VALUE]", &[i!("VALUE", I::WholeNumber), i!("SUBSTACK", I::SubStack)], &[f!("VARIABLE", F::Variable)]),
+        op("control_stop",                 S::Cap,       "stop [STOP_OPTION]",             &[], &[f!("STOP_OPTION", F::Options(&["all", "this script", "other scripts in sprite", "other scripts in stage"]))]),
+        op("control_start_as_clone",       S::Hat,       "when I start as a clone",        &[], &[]),
+        op("control_create_clone_of",      S::Stack,     "create clone of [CLONE_OPTION]", &[i!("CLONE_OPTION", I::Menu("control_create_clone_of_menu"))], &[]),
+        op("control_create_clone_of_menu", S::Reporter,  "[CLONE_OPTION]",                 &[], &[f!("CLONE_OPTION", F::Sprite(&["_myself_"]))]),
+        op("control_delete_this_clone",    S::Cap,       "delete this clone",              &[], &[]),
+        op("control_get_counter",          S::Reporter,  "counter",                        &[], &[]),
+        op("control_incr_counter",         S::Stack,     "increment counter",              &[], &[]),
+        op("control_clear_counter",        S::Stack,     "clear counter",                  &[], &[]),
+        op("control_all_at_once",          S::CBlock,    "all at once",                    &[i!("SUBSTACK", I::SubStack)], &[]),
+
+        // Operators ===========================================================
+        op("operator_add",       S::Reporter, "[NUM1] + [NUM2]",                  &[i!("NUM1", I::Number), i!("NUM2", I::Number)], &[]),
+        op("operator_subtract",  S::Reporter, "[NUM1] - [NUM2]",                  &[i!("NUM1", I::Number), i!("NUM2", I::Number)], &[]),
+        op("operator_multiply",  S::Reporter, "[NUM1] * [NUM2]",                  &[i!("NUM1", I::Number), i!("NUM2", I::Number)], &[]),
+        op("operator_divide",    S::Reporter, "[NUM1] / [NUM2]",                  &[i!("NUM1", I::Number), i!("NUM2", I::Number)], &[]),
+        op("operator_random",    S::Reporter, "pick random [FROM] to [TO]",       &[i!("FROM", I::Number), i!("TO", I::Number)], &[]),
+        op("operator_gt",        S::Boolean,  "[OPERAND1] > [OPERAND2]",          &[i!("OPERAND1", I::Text), i!("OPERAND2", I::Text)], &[]),
+        op("operator_lt",        S::Boolean,  "[OPERAND1] < [OPERAND2]",          &[i!("OPERAND1", I::Text), i!("OPERAND2", I::Text)], &[]),
+        op("operator_equals",    S::Boolean,  "[OPERAND1] = [OPERAND2]",          &[i!("OPERAND1", I::Text), i!("OPERAND2", I::Text)], &[]),
+        op("operator_and",       S::Boolean,  "[OPERAND1] and [OPERAND2]",        &[i!("OPERAND1", I::Boolean), i!("OPERAND2", I::Boolean)], &[]),
+        op("operator_or",        S::Boolean,  "[OPERAND1] or [OPERAND2]",         &[i!("OPERAND1", I::Boolean), i!("OPERAND2", I::Boolean)], &[]),
+        op("operator_not",       S::Boolean,  "not [OPERAND]",                    &[i!("OPERAND", I::Boolean)], &[]),
+        op("operator_join",      S::Reporter, "join [STRING1] [STRING2]",         &[i!("STRING1", I::Text), i!("STRING2", I::Text)], &[]),
+        op("operator_letter_of", S::Reporter, "letter [LETTER] of [STRING]",      &[i!("LETTER", I::WholeNumber), i!("STRING", I::Text)], &[]),
+        op("operator_length",    S::Reporter, "length of [STRING]",               &[i!("STRING", I::Text)], &[]),
+        op("operator_contains",  S::Boolean,  "[STRING1] contains [STRING2]?",    &[i!("STRING1", I::Text), i!("STRING2", I::Text)], &[]),
+        op("operator_mod",       S::Reporter, "[NUM1] mod [NUM2]",                &[i!("NUM1", I::Number), i!("NUM2", I::Number)], &[]),
+        op("operator_round",     S::Reporter, "round [NUM]",                      &[i!("NUM", I::Number)], &[]),
+        op("operator_mathop",    S::Reporter, "[OPERATOR] of [NUM]",              &[i!("NUM", I::Number)], &[f!("OPERATOR", F::Options(MATH_OPS))]),
+
+        // Sensing =============================================================
+        op("sensing_touchingobject",       S::Boolean,  "touching [TOUCHINGOBJECTMENU]?",      &[i!("TOUCHINGOBJECTMENU", I::Menu("sensing_touchingobjectmenu"))], &[]),
+        op("sensing_touchingobjectmenu",   S::Reporter, "[TOUCHINGOBJECTMENU]",                &[], &[f!("TOUCHINGOBJECTMENU", F::Sprite(&["_mouse_", "_edge_"]))]),
+        op("sensing_touchingcolor",        S::Boolean,  "touching color [COLOR]?",             &[i!("COLOR", I::Color)], &[]),
+        op("sensing_coloristouchingcolor", S::Boolean,  "color [COLOR] is touching [COLOR2]?", &[i!("COLOR", I::Color), i!("COLOR2", I::Color)], &[]),
+        op("sensing_distanceto",           S::Reporter, "distance to [DISTANCETOMENU]",        &[i!("DISTANCETOMENU", I::Menu("sensing_distancetomenu"))], &[]),
+        op("sensing_distancetomenu",       S::Reporter, "[DISTANCETOMENU]",                    &[], &[f!("DISTANCETOMENU", F::Sprite(&["_mouse_"]))]),
+        op("sensing_askandwait",           S::Stack,    "ask [QUESTION] and wait",             &[i!("QUESTION", I::Text)], &[]),
+        op("sensing_answer",               S::Reporter, "answer",                              &[], &[]),
+        op("sensing_keypressed",           S::Boolean,  "key [KEY_OPTION] pressed?",           &[i!("KEY_OPTION", I::Menu("sensing_keyoptions"))], &[]),
+        op("sensing_keyoptions",           S::Reporter, "[KEY_OPTION]",                        &[], &[f!("KEY_OPTION", F::Options(KEYS))]),
+        op("sensing_mousedown",            S::Boolean,  "mouse down?",                         &[], &[]),
+        op("sensing_mousex",               S::Reporter, "mouse x",                             &[], &[]),
+        op("sensing_mousey",               S::Reporter, "mouse y",                             &[], &[]),
+        op("sensing_setdragmode",          S::Stack,    "set drag mode [DRAG_MODE]",           &[], &[f!("DRAG_MODE", F::Options(&["draggable", "not draggable"]))]),
+        op("sensing_loudness",             S::Reporter, "loudness",                            &[], &[]),
+        op("sensing_loud",                 S::Boolean,  "loud?",                               &[], &[]),
+        op("sensing_timer",                S::Reporter, "timer",                               &[], &[]),
+        op("sensing_resettimer",           S::Stack,    "reset timer",                         &[], &[]),
+        op("sensing_of",                   S::Reporter, "[PROPERTY] of [OBJECT]",              &[i!("OBJECT", I::Menu("sensing_of_object_menu"))], &[f!("PROPERTY", F::Text)]),
+        op("sensing_of_object_menu",       S::Reporter, "[OBJECT]",                            &[], &[f!("OBJECT", F::Sprite(&["_stage_"]))]),
+        op("sensing_current",              S::Reporter, "current [CURRENTMENU]",               &[], &[f!("CURRENTMENU", F::Options(CURRENT))]),
+        op("sensing_dayssince2000",        S::Reporter, "days since 2000",                     &[], &[]),
+        op("sensing_username",             S::Reporter, "username",                            &[], &[]),
+        op("sensing_userid",               S::Reporter, "user id",                             &[], &[]),
+
+        // Data ================================================================
+        op("data_variable",          S::Reporter, "[VARIABLE]",                            &[], &[f!("VARIABLE", F::Variable)]),
+        op("data_setvariableto",     S::Stack,    "set [VARIABLE] to [VALUE]",             &[i!("VALUE", I::Text)], &[f!("VARIABLE", F::Variable)]),
+        op("data_changevariableby",  S::Stack,    "change [VARIABLE] by [VALUE]",          &[i!("VALUE", I::Number)], &[f!("VARIABLE", F::Variable)]),
+        op("data_showvariable",      S::Stack,    "show variable [VARIABLE]",              &[], &[f!("VARIABLE", F::Variable)]),
+        op("data_hidevariable",      S::Stack,    "hide variable [VARIABLE]",              &[], &[f!("VARIABLE", F::Variable)]),
+        op("data_listcontents",      S::Reporter, "[LIST]",                                &[], &[f!("LIST", F::List)]),
+        op("data_listindexall",      S::Reporter, "[INDEX]",                               &[], &[f!("INDEX", F::Text)]),
+        op("data_listindexrandom",   S::Reporter, "[INDEX]",                               &[], &[f!("INDEX", F::Text)]),
+        op("data_addtolist",         S::Stack,    "add [ITEM] to [LIST]",                  &[i!("ITEM", I::Text)], &[f!("LIST", F::List)]),
+        op("data_deleteoflist",      S::Stack,    "delete [INDEX] of [LIST]",              &[i!("INDEX", I::Integer)], &[f!("LIST", F::List)]),
+        op("data_deletealloflist",   S::Stack,    "delete all of [LIST]",                  &[], &[f!("LIST", F::List)]),
+        op("data_insertatlist",      S::Stack,    "insert [ITEM] at [INDEX] of [LIST]",    &[i!("ITEM", I::Text), i!("INDEX", I::Integer)], &[f!("LIST", F::List)]),
+        op("data_replaceitemoflist", S::Stack,    "replace item [INDEX] of [LIST] with [ITEM]", &[i!("INDEX", I::Integer), i!("ITEM", I::Text)], &[f!("LIST", F::List)]),
+        op("data_itemoflist",        S::Reporter, "item [INDEX] of [LIST]",                &[i!("INDEX", I::Integer)], &[f!("LIST", F::List)]),
+        op("data_itemnumoflist",     S::Reporter, "item # of [ITEM] in [LIST]",            &[i!("ITEM", I::Text)], &[f!("LIST", F::List)]),
+        op("data_lengthoflist",      S::Reporter, "length of [LIST]",                      &[], &[f!("LIST", F::List)]),
+        op("data_listcontainsitem",  S::Boolean,  "[LIST] contains [ITEM]?",               &[i!("ITEM", I::Text)], &[f!("LIST", F::List)]),
+        op("data_showlist",          S::Stack,    "show list [LIST]",                      &[], &[f!("LIST", F::List)]),
+        op("data_hidelist",          S::Stack,    "hide list [LIST]",                      &[], &[f!("LIST", F::List)]),
+
+        // My Blocks ===========================================================
+        op("procedures_definition",           S::Hat,      "define [custom_block]", &[i!("custom_block", I::Menu("procedures_prototype"))], &[]),
+        op("procedures_prototype",            S::Reporter, "",                      &[], &[]),
+        op("procedures_declaration",          S::Reporter, "",                      &[], &[]),
+        op("procedures_call",                 S::Stack,    "",                      &[], &[]),
+        op("argument_reporter_string_number", S::Reporter, "[VALUE]",               &[], &[f!("VALUE", F::Text)]),
+        op("argument_reporter_boolean",       S::Boolean,  "[VALUE]",               &[], &[f!("VALUE", F::Text)]),
+        op("argument_editor_string_number",   S::Reporter, "[TEXT]",                &[], &[f!("TEXT", F::Text)]),
+        op("argument_editor_boolean",         S::Boolean,  "[TEXT]",                &[], &[f!("TEXT", F::Text)]),
+
+        // Pen =================================================================
+        ext("pen", "pen_clear",                 S::Stack,    "erase all",                        &[], &[]),
+        ext("pen", "pen_stamp",                 S::Stack,    "stamp",                            &[], &[]),
+        ext("pen", "pen_penDown",               S::Stack,    "pen down",                         &[], &[]),
+        ext("pen", "pen_penUp",                 S::Stack,    "pen up",                           &[], &[]),
+        ext("pen", "pen_setPenColorToColor",    S::Stack,    "set pen color to [COLOR]",         &[i!("COLOR", I::Color)], &[]),
+        ext("pen", "pen_changePenColorParamBy", S::Stack,    "change pen [COLOR_PARAM] by [VALUE]", &[i!("COLOR_PARAM", I::Menu("pen_menu_colorParam")), i!("VALUE", I::Number)], &[]),
+        ext("pen", "pen_setPenColorParamTo",    S::Stack,    "set pen [COLOR_PARAM] to [VALUE]", &[i!("COLOR_PARAM", I::Menu("pen_menu_colorParam")), i!("VALUE", I::Number)], &[]),
+        ext("pen", "pen_menu_colorParam",       S::Reporter, "[colorParam]",                     &[], &[f!("colorParam", F::Options(&["color", "saturation", "brightness", "transparency"]))]),
+        ext("pen", "pen_changePenSizeBy",       S::Stack,    "change pen size by [SIZE]",        &[i!("SIZE", I::Number)], &[]),
+        ext("pen", "pen_setPenSizeTo",          S::Stack,    "set pen size to [SIZE]",           &[i!("SIZE", I::Number)], &[]),
+        ext("pen", "pen_setPenShadeToNumber",   S::Stack,    "set pen shade to [SHADE]",         &[i!("SHADE", I::Number)], &[]),
+        ext("pen", "pen_changePenShadeBy",      S::Stack,    "change pen shade by [SHADE]",      &[i!("SHADE", I::Number)], &[]),
+        ext("pen", "pen_setPenHueToNumber",     S::Stack,    "set pen color to [HUE]",           &[i!("HUE", I::Number)], &[]),
+        ext("pen", "pen_changePenHueBy",        S::Stack,    "change pen color by [HUE]",        &[i!("HUE", I::Number)], &[]),
+
+        // Video Sensing =======================================================
+        ext("videoSensing", "videoSensing_whenMotionGreaterThan", S::Hat,      "when video motion > [REFERENCE]",          &[i!("REFERENCE", I::Number)], &[]),
+        ext("videoSensing", "videoSensing_videoOn",               S::Reporter, "video [ATTRIBUTE] on [SUBJECT]",           &[i!("ATTRIBUTE", I::Menu("videoSensing_menu_ATTRIBUTE")), i!("SUBJECT", I::Menu("videoSensing_menu_SUBJECT"))], &[]),
+        ext("videoSensing", "videoSensing_menu_ATTRIBUTE",        S::Reporter, "[ATTRIBUTE]",                              &[], &[f!("ATTRIBUTE", F::Options(&["motion", "direction"]))]),
+        ext("videoSensing", "videoSensing_menu_SUBJECT",          S::Reporter, "[SUBJECT]",                                &[], &[f!("SUBJECT", F::Options(&["this sprite", "Stage"]))]),
+        ext("videoSensing", "videoSensing_videoToggle",           S::Stack,    "turn video [VIDEO_STATE]",                 &[i!("VIDEO_STATE", I::Menu("videoSensing_menu_VIDEO_STATE"))], &[]),
+        ext("videoSensing", "videoSensing_menu_VIDEO_STATE",      S::Reporter, "[VIDEO_STATE]",                            &[], &[f!("VIDEO_STATE", F::Options(&["off", "on", "on-flipped"]))]),
+        ext("videoSensing", "videoSensing_setVideoTransparency",  S::Stack,    "set video transparency to [TRANSPARENCY]", &[i!("TRANSPARENCY", I::Number)], &[]),
+
+        // Translate ===========================================================
+        ext("translate", "translate_getTranslate",      S::Reporter, "translate [WORDS] to [LANGUAGE]", &[i!("WORDS", I::Text), i!("LANGUAGE", I::Menu("translate_menu_languages"))], &[]),
+        ext("translate", "translate_menu_languages",    S::Reporter, "[languages]",                     &[], &[f!("languages", F::Text)]),
+        ext("translate", "translate_getViewerLanguage", S::Reporter, "language",                        &[], &[]),
+    ]
+};
+
+/// `(opcode, input or menu field, value)`, see [`palette_default`]
+#[rustfmt::skip]
+static PALETTE_DEFAULTS: &[(&str, &str, &str)] = &[
+    // Motion ==================================================================
+    ("motion_movesteps",         "STEPS",     "10"),
+    ("motion_turnright",         "DEGREES",   "15"),
+    ("motion_turnleft",          "DEGREES",   "15"),
+    ("motion_gotoxy",            "X",         "0"),
+    ("motion_gotoxy",            "Y",         "0"),
+    ("motion_glideto",           "SECS",      "1"),
+    ("motion_glidesecstoxy",     "SECS",      "1"),
+    ("motion_glidesecstoxy",     "X",         "0"),
+    ("motion_glidesecstoxy",     "Y",         "0"),
+    ("motion_pointindirection",  "DIRECTION", "90"),
+    ("motion_pointtowards_menu", "TOWARDS",   "_mouse_"),
+    ("motion_changexby",         "DX",        "10"),
+    ("motion_setx",              "X",         "0"),
+    ("motion_changeyby",         "DY",        "10"),
+    ("motion_sety",              "Y",         "0"),
+
+    // Looks ===================================================================
+    ("looks_sayforsecs",              "MESSAGE",  "Hello!"),
+    ("looks_sayforsecs",              "SECS",     "2"),
+    ("looks_say",                     "MESSAGE",  "Hello!"),
+    ("looks_thinkforsecs",            "MESSAGE",  "Hmm..."),
+    ("looks_thinkforsecs",            "SECS",     "2"),
+    ("looks_think",                   "MESSAGE",  "Hmm..."),
+    ("looks_costume",                 "COSTUME",  "costume1"),
+    ("looks_backdrops",               "BACKDROP", "backdrop1"),
+    ("looks_changesizeby",            "CHANGE",   "10"),
+    ("looks_setsizeto",               "SIZE",     "100"),
+    ("looks_changeeffectby",          "CHANGE",   "25"),
+    ("looks_seteffectto",             "VALUE",    "0"),
+    ("looks_goforwardbackwardlayers", "NUM",      "1"),
+
+    // Sound ===================================================================
+    ("sound_changeeffectby", "VALUE",  "10"),
+    ("sound_seteffectto",    "VALUE",  "100"),
+    ("sound_changevolumeby", "VOLUME", "-10"),
+    ("sound_setvolumeto",    "VOLUME", "100"),
+
+    // Events ==================================================================
+    ("event_whenbackdropswitchesto", "BACKDROP", "backdrop1"),
+    ("event_whengreaterthan",        "VALUE",    "10"),
+
+    // Control =================================================================
+    ("control_wait",   "DURATION", "1"),
+    ("control_repeat", "TIMES",    "10"),
+
+    // Operators ===============================================================
+    ("operator_random",    "FROM",     "1"),
+    ("operator_random",    "TO",       "10"),
+    ("operator_gt",        "OPERAND2", "50"),
+    ("operator_lt",        "OPERAND2", "50"),
+    ("operator_equals",    "OPERAND2", "50"),
+    ("operator_join",      "STRING1",  "apple "),
+    ("operator_join",      "STRING2",  "banana"),
+    ("operator_letter_of", "LETTER",   "1"),
+    ("operator_letter_of", "STRING",   "apple"),
+    ("operator_length",    "STRING",   "apple"),
+    ("operator_contains",  "STRING1",  "apple"),
+    ("operator_contains",  "STRING2",  "a"),
+
+    // Sensing =================================================================
+    ("sensing_askandwait", "QUESTION", "What's your name?"),
+
+    // Data ====================================================================
+    ("data_setvariableto",     "VALUE", "0"),
+    ("data_changevariableby",  "VALUE", "1"),
+    ("data_addtolist",         "ITEM",  "thing"),
+    ("data_deleteoflist",      "INDEX", "1"),
+    ("data_insertatlist",      "ITEM",  "thing"),
+    ("data_insertatlist",      "INDEX", "1"),
+    ("data_replaceitemoflist", "INDEX", "1"),
+    ("data_replaceitemoflist", "ITEM",  "thing"),
+    ("data_itemoflist",        "INDEX", "1"),
+    ("data_itemnumoflist",     "ITEM",  "thing"),
+    ("data_listcontainsitem",  "ITEM",  "thing"),
+
+    // Pen =====================================================================
+    ("pen_changePenColorParamBy", "VALUE", "10"),
+    ("pen_setPenColorParamTo",    "VALUE", "50"),
+    ("pen_changePenSizeBy",       "SIZE",  "1"),
+    ("pen_setPenSizeTo",          "SIZE",  "1"),
+    ("pen_setPenShadeToNumber",   "SHADE", "50"),
+    ("pen_changePenShadeBy",      "SHADE", "10"),
+    ("pen_setPenHueToNumber",     "HUE",   "0"),
+    ("pen_changePenHueBy",        "HUE",   "10"),
+
+    // Video Sensing ===========================================================
+    ("videoSensing_whenMotionGreaterThan", "REFERENCE",    "10"),
+    ("videoSensing_menu_ATTRIBUTE",        "ATTRIBUTE",    "motion"),
+    ("videoSensing_menu_SUBJECT",          "SUBJECT",      "this sprite"),
+    ("videoSensing_menu_VIDEO_STATE",      "VIDEO_STATE",  "on"),
+    ("videoSensing_setVideoTransparency",  "TRANSPARENCY", "50"),
+
+    // Translate ===============================================================
+    ("translate_getTranslate",   "WORDS",     "hello"),
+    ("translate_menu_languages", "languages", "en"),
+];
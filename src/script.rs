@@ -0,0 +1,231 @@
+//! Writing scripts as Rust closures
+//!
+//! Each call on [`Script`] adds blocks to the end of the script,
+//! and closures given to C-blocks are traced into their `SUBSTACK`.
+//! Rust's `if` and `for` decides which blocks are added.
+//!
+//! Operators on reporters come from [`Expr`].
+//!
+//! ```
+//! # use sb_itchy::{blocks, script::{script, Expr}};
+//! let count = 3;
+//! let stack = script(|s| {
+//!     s.say("hi");
+//!     s.if_(blocks::mouse_down(), |s| s.hide());
+//!     s.if_(blocks::x_position().gt(100), |s| s.say("too far"));
+//!     for _ in 0..count {
+//!         s.add(blocks::next_costume());
+//!     }
+//! });
+//! assert_eq!(stack.stack.len(), 3 + count);
+//! ```
+
+use crate::{
+    block::{BlockFieldBuilder, BlockInputBuilder},
+    blocks::{self, control_flow},
+    stack::StackBuilder,
+};
+
+type Bfb = BlockFieldBuilder;
+type Bib = BlockInputBuilder;
+
+/// Run `f` and collect the blocks it adds
+pub fn script<F: FnOnce(&mut Script)>(f: F) -> StackBuilder {
+    let mut script = Script::default();
+    f(&mut script);
+    script.stack
+}
+
+/// Blocks added so far, see [`script`]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Script {
+    stack: StackBuilder,
+}
+
+impl Script {
+    /// Add any blocks, like the ones from [`blocks`]
+    pub fn add(&mut self, stack: StackBuilder) {
+        self.stack.stack.extend(stack.stack);
+    }
+
+    pub fn say<I: Into<Bib>>(&mut self, message: I) {
+        let message: Bib = message.into();
+        self.add(blocks::say(message));
+    }
+
+    pub fn think<I: Into<Bib>>(&mut self, message: I) {
+        let message: Bib = message.into();
+        self.add(blocks::think(message));
+    }
+
+    pub fn show(&mut self) {
+        self.add(blocks::show());
+    }
+
+    pub fn hide(&mut self) {
+        self.add(blocks::hide());
+    }
+
+    pub fn wait<I: Into<Bib>>(&mut self, duration: I) {
+        let duration: Bib = duration.into();
+        self.add(blocks::wait(duration));
+    }
+
+    pub fn set_var<I: Into<Bib>>(&mut self, var: Bfb, to: I) {
+        let to: Bib = to.into();
+        self.add(blocks::set_var_to(var, to));
+    }
+
+    pub fn change_var<I: Into<Bib>>(&mut self, var: Bfb, by: I) {
+        let by: Bib = by.into();
+        self.add(blocks::change_var_by(var, by));
+    }
+
+    pub fn if_<C, F>(&mut self, condition: C, then: F)
+    where
+        C: Into<Bib>,
+        F: FnOnce(&mut Script),
+    {
+        self.add(blocks::if_(condition.into(), substack(then)));
+    }
+
+    pub fn if_else<C, F, G>(&mut self, condition: C, then: F, otherwise: G)
+    where
+        C: Into<Bib>,
+        F: FnOnce(&mut Script),
+        G: FnOnce(&mut Script),
+    {
+        self.add(blocks::if_else(
+            condition.into(),
+            substack(then),
+            substack(otherwise),
+        ));
+    }
+
+    pub fn repeat<I, F>(&mut self, times: I, body: F)
+    where
+        I: Into<Bib>,
+        F: FnOnce(&mut Script),
+    {
+        let times: Bib = times.into();
+        self.add(blocks::repeat(times, substack(body)));
+    }
+
+    pub fn repeat_until<C, F>(&mut self, condition: C, body: F)
+    where
+        C: Into<Bib>,
+        F: FnOnce(&mut Script),
+    {
+        self.add(blocks::repeat_until(condition.into(), substack(body)));
+    }
+
+    pub fn forever<F: FnOnce(&mut Script)>(&mut self, body: F) {
+        self.add(blocks::forever(substack(body)));
+    }
+
+    /// See [`control_flow::repeat_while`]
+    pub fn repeat_while<C, F>(&mut self, condition: C, body: F)
+    where
+        C: Into<Bib>,
+        F: FnOnce(&mut Script),
+    {
+        self.add(control_flow::repeat_while(condition.into(), script(body)));
+    }
+
+    /// See [`control_flow::for_range`]
+    pub fn for_range<A, B, C, F>(&mut self, var: Bfb, from: A, to: B, step: C, body: F)
+    where
+        A: Into<Bib>,
+        B: Into<Bib>,
+        C: Into<Bib>,
+        F: FnOnce(&mut Script),
+    {
+        self.add(control_flow::for_range(
+            var,
+            from.into(),
+            to.into(),
+            step.into(),
+            script(body),
+        ));
+    }
+
+    /// See [`control_flow::for_each_item`]
+    pub fn for_each_item<F: FnOnce(&mut Script)>(
+        &mut self,
+        list: Bfb,
+        var: Bfb,
+        index: Bfb,
+        body: F,
+    ) {
+        self.add(control_flow::for_each_item(list, var, index, script(body)));
+    }
+}
+
+/// Nothing in the C-block when `f` doesn't add anything
+fn substack<F: FnOnce(&mut Script)>(f: F) -> Option<Bib> {
+    let stack = script(f);
+    if stack.stack.is_empty() {
+        None
+    } else {
+        Some(Bib::stack(stack))
+    }
+}
+
+/// Operator blocks with `self` on the left, so `x.gt(5)` is `x > 5`
+pub trait Expr: Into<Bib> + Sized {
+    fn add<I: Into<Bib>>(self, rhs: I) -> StackBuilder {
+        blocks::add(input(self), input(rhs))
+    }
+
+    fn sub<I: Into<Bib>>(self, rhs: I) -> StackBuilder {
+        blocks::sub(input(self), input(rhs))
+    }
+
+    fn mul<I: Into<Bib>>(self, rhs: I) -> StackBuilder {
+        blocks::mul(input(self), input(rhs))
+    }
+
+    fn div<I: Into<Bib>>(self, rhs: I) -> StackBuilder {
+        blocks::div(input(self), input(rhs))
+    }
+
+    fn modulo<I: Into<Bib>>(self, rhs: I) -> StackBuilder {
+        blocks::modulo(input(self), input(rhs))
+    }
+
+    fn join<I: Into<Bib>>(self, rhs: I) -> StackBuilder {
+        blocks::join(input(self), input(rhs))
+    }
+
+    fn gt<I: Into<Bib>>(self, rhs: I) -> StackBuilder {
+        blocks::greater_than(input(self), input(rhs))
+    }
+
+    fn lt<I: Into<Bib>>(self, rhs: I) -> StackBuilder {
+        blocks::less_than(input(self), input(rhs))
+    }
+
+    /// Named `equals` like the block since `eq` is taken by [`PartialEq`]
+    fn equals<I: Into<Bib>>(self, rhs: I) -> StackBuilder {
+        blocks::equals(input(self), input(rhs))
+    }
+
+    fn and<I: Into<Bib>>(self, rhs: I) -> StackBuilder {
+        blocks::and(input(self), input(rhs))
+    }
+
+    fn or<I: Into<Bib>>(self, rhs: I) -> StackBuilder {
+        blocks::or(input(self), input(rhs))
+    }
+
+    fn not(self) -> StackBuilder {
+        blocks::not(input(self))
+    }
+}
+
+impl Expr for StackBuilder {}
+impl Expr for Bib {}
+
+fn input(value: impl Into<Bib>) -> Bib {
+    value.into()
+}
@@ -3,7 +3,9 @@ use std::collections::HashMap;
 use sb_sbity::{block::Block, comment::Comment};
 
 use crate::{
-    block::{BlockBuilder, BlockNormalBuilder, BlockVarListBuilder},
+    block::{
+        BlockBuilder, BlockInputBuilder, BlockNormalBuilder, BlockVarListBuilder, StackOrValue,
+    },
     build_context::TargetContext,
     uid::Uid,
 };
@@ -23,6 +25,7 @@ impl StackBuilder {
     }
 
     /// Varlist is a reporter. You shouldn't continue after this... but nothing disllowed you.
+    /// [`TypedStack`](crate::typed_stack::TypedStack) does.
     pub fn start_varlist(block: BlockVarListBuilder) -> StackBuilder {
         StackBuilder::start_with_capacity(1, BlockBuilder::VarList(block))
     }
@@ -61,6 +64,52 @@ impl StackBuilder {
         self
     }
 
+    pub fn top_block_position(&self) -> (Option<f64>, Option<f64>) {
+        match self.stack.first() {
+            Some(BlockBuilder::Normal(n)) => n.pos(),
+            Some(BlockBuilder::VarList(vl)) => (vl.x, vl.y),
+            Some(BlockBuilder::CustomBlock(c)) => c.pos(),
+            Some(BlockBuilder::CustomBlockCall(c)) => c.pos(),
+            None => (None, None),
+        }
+    }
+
+    /// Stacks without a position are placed by [`LayoutOptions`](crate::layout::LayoutOptions)
+    pub fn has_position(&self) -> bool {
+        let (x, y) = self.top_block_position();
+        x.is_some() || y.is_some()
+    }
+
+    /// Visit every block in the stack and in the stacks put in their inputs.
+    pub fn walk(&self, f: &mut dyn FnMut(&BlockBuilder)) {
+        for block in &self.stack {
+            f(block);
+            let inputs: Vec<&BlockInputBuilder> = match block {
+                BlockBuilder::Normal(n) => n.inputs().values().collect(),
+                BlockBuilder::CustomBlockCall(c) => c.args.iter().map(|(_, input)| input).collect(),
+                BlockBuilder::CustomBlock(_) | BlockBuilder::VarList(_) => vec![],
+            };
+            for input in inputs {
+                for value in &input.values {
+                    if let Some(StackOrValue::Stack(stack)) = value {
+                        stack.walk(f);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Proccodes of the custom blocks called in this stack
+    pub fn custom_block_calls(&self) -> Vec<String> {
+        let mut calls = vec![];
+        self.walk(&mut |block| {
+            if let BlockBuilder::CustomBlockCall(c) = block {
+                calls.push(c.proccode().to_owned());
+            }
+        });
+        calls
+    }
+
     pub fn build(
         self,
         first_block_uid: &Uid,
@@ -116,8 +165,17 @@ impl StackBuilder {
             acc + block.calc_block_height(data, is_input)
         }) + if is_input { 0. } else { data.block_bump }
     }
+
+    /// Width of the widest block in the stack
+    pub fn calc_block_width(&self, data: &BlockWidthData) -> f64 {
+        self.stack
+            .iter()
+            .map(|block| block.calc_block_width(data))
+            .fold(0., f64::max)
+    }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct BlockHeightData {
     pub input_block_height: f64,
     pub input_block_nest_height: f64,
@@ -141,3 +199,34 @@ impl Default for BlockHeightData {
         }
     }
 }
+
+/// Widths are estimated from the block's label in the [`registry`](crate::registry)
+/// and what's put in its inputs and fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockWidthData {
+    /// Width of a character in a label, a field or an input
+    pub char_width: f64,
+    /// Space around the value of an input
+    pub input_padding: f64,
+    /// Width of an empty input or an input with a short value
+    pub min_input_width: f64,
+    /// Space for the arrow of a dropdown
+    pub dropdown_width: f64,
+    /// Space at both sides of a block
+    pub block_padding: f64,
+    /// How far blocks in a C-block is pushed right
+    pub nest_indent: f64,
+}
+
+impl Default for BlockWidthData {
+    fn default() -> Self {
+        BlockWidthData {
+            char_width: 16.,
+            input_padding: 32.,
+            min_input_width: 80.,
+            dropdown_width: 48.,
+            block_padding: 32.,
+            nest_indent: 32.,
+        }
+    }
+}
@@ -8,19 +8,27 @@ use sb_sbity::{
     list::List,
     string_hashmap::StringHashMap,
     target::{RotationStyle, Sprite, Stage, Target, VideoState},
+    value::Value,
     variable::Variable,
 };
 
 use crate::{
     asset::{CostumeBuilder, SoundBuilder},
+    block::BlockBuilder,
     build_context::TargetContext,
     comment::CommentBuilder,
     data::{ListBuilder, VariableBuilder},
+    layout::LayoutOptions,
+    lower::lower_reporter_calls,
     resource::Resource,
     stack::StackBuilder,
     uid::Uid,
 };
-use crate::{build_context::GlobalVarListContext, custom_block::CustomBlockTy};
+use crate::{
+    blocks::define_custom_block,
+    build_context::GlobalVarListContext,
+    custom_block::{locals_list, CustomBlockHandle, CustomBlockTy, SharedCustomBlock},
+};
 
 #[rustfmt::skip]
 #[derive(Debug, Clone, PartialEq)]
@@ -30,13 +38,18 @@ pub struct TargetBuilder {
     pub lists:           HashMap<String, ListBuilder>,
     pub broadcasts:      HashMap<String, Uid>,
     pub block_stackes:   Vec<StackBuilder>,
-    pub comments:        HashMap<Uid, Comment>,
+    /// Comments that isn't attached to any block
+    pub comments:        Vec<CommentBuilder>,
     pub costumes:        Vec<CostumeBuilder>,
     pub sounds:          Vec<SoundBuilder>,
     pub current_costume: u64,
-    pub layer_order:     u64,
+    /// [`None`] to let [`ProjectBuilder`](crate::project::ProjectBuilder) decide.
+    /// Stage is always at 0.
+    pub layer_order:     Option<u64>,
     pub volume:          f64,
     pub custom_blocks:   Vec<CustomBlockTy>,
+    /// How stacks without a position are placed
+    pub layout:          LayoutOptions,
 }
 
 impl TargetBuilder {
@@ -51,7 +64,7 @@ impl TargetBuilder {
     }
 
     pub fn set_layer_order(&mut self, layer: u64) -> &mut Self {
-        self.layer_order = layer;
+        self.layer_order = Some(layer);
         self
     }
 
@@ -74,14 +87,91 @@ impl TargetBuilder {
         self
     }
 
+    pub fn set_layout(&mut self, layout: LayoutOptions) -> &mut Self {
+        self.layout = layout;
+        self
+    }
+
     pub fn add_block_stack(&mut self, stack_builder: StackBuilder) -> &mut Self {
         self.block_stackes.push(stack_builder);
         self
     }
 
+    /// Make the custom block callable in this target without defining it.
+    /// Use this when the definition is added with [`TargetBuilder::add_block_stack`] yourself,
+    /// wrap its body with [`CustomBlockTy::push_locals`] and [`CustomBlockTy::pop_locals_at_exits`] if it has locals.
+    pub fn add_custom_block(&mut self, signature: CustomBlockTy) -> &mut Self {
+        self.custom_blocks.push(signature);
+        self
+    }
+
+    /// Add the custom block and its definition script with `body` under it.
+    ///
+    /// Locals of the custom block are added and removed around `body`
+    /// and its return variable is added if `body` sets it.
+    pub fn define_custom_block(
+        &mut self,
+        signature: CustomBlockTy,
+        body: StackBuilder,
+    ) -> CustomBlockHandle {
+        let handle = CustomBlockHandle::new(signature.clone());
+        let mut returns = false;
+        body.walk(&mut |block| {
+            if let BlockBuilder::Normal(n) = block {
+                returns |= n
+                    .fields()
+                    .get("VARIABLE")
+                    .is_some_and(|var| var.value == handle.return_variable());
+            }
+        });
+        if returns {
+            self.variables
+                .entry(handle.return_variable())
+                .or_insert_with(|| VariableBuilder::new(Value::Text("".into())));
+        }
+        if !signature.locals().is_empty() {
+            self.lists
+                .entry(locals_list(&signature.proccode()))
+                .or_insert_with(|| ListBuilder::new(vec![]));
+        }
+        self.block_stackes.push(
+            define_custom_block(signature.proccode())
+                .next(signature.push_locals())
+                .next(signature.pop_locals_at_exits(body)),
+        );
+        self.custom_blocks.push(signature);
+        handle
+    }
+
+    /// Define the custom blocks from `library` that this target calls, including the ones they call.
+    /// Custom blocks that this target already has are left alone.
+    pub(crate) fn add_used_custom_blocks(&mut self, library: &[SharedCustomBlock]) {
+        let mut calls: Vec<String> = self
+            .block_stackes
+            .iter()
+            .flat_map(StackBuilder::custom_block_calls)
+            .collect();
+        while let Some(proccode) = calls.pop() {
+            if self
+                .custom_blocks
+                .iter()
+                .any(|ty| ty.proccode() == proccode)
+            {
+                continue;
+            }
+            let Some(shared) = library
+                .iter()
+                .find(|shared| shared.signature.proccode() == proccode)
+            else {
+                continue;
+            };
+            calls.extend(shared.body.custom_block_calls());
+            self.define_custom_block(shared.signature.with_new_ids(), shared.body.clone());
+        }
+    }
+
     pub fn add_comment(&mut self, comment_builder: CommentBuilder) -> &mut Self {
-        let comment = comment_builder.build();
-        self.comments.insert(Uid::generate(), comment);
+        self.comments.push(comment_builder);
         self
     }
 
@@ -105,10 +195,10 @@ impl TargetBuilder {
     ) -> (Target, Option<GlobalVarListContext>) {
         let TargetBuilder {
             name,
-            variables,
-            lists,
+            mut variables,
+            mut lists,
             broadcasts,
-            block_stackes,
+            mut block_stackes,
             comments,
             costumes,
             sounds,
@@ -116,7 +206,19 @@ impl TargetBuilder {
             layer_order,
             volume,
             custom_blocks,
+            layout,
         } = self;
+        let (used_variables, used_lists) = lower_reporter_calls(&mut block_stackes);
+        for name in used_variables {
+            variables
+                .entry(name)
+                .or_insert_with(|| VariableBuilder::new(Value::Text("".into())));
+        }
+        for name in used_lists {
+            lists
+                .entry(name)
+                .or_insert_with(|| ListBuilder::new(vec![]));
+        }
         let variables: HashMap<String, Variable> = variables
             .into_iter()
             .map(|(var_name, var_builder)| {
@@ -137,6 +239,11 @@ impl TargetBuilder {
             .collect();
 
         let mut comments = comments;
+        layout.arrange(&mut block_stackes, &mut comments);
+        let mut comments: HashMap<Uid, Comment> = comments
+            .into_iter()
+            .map(|comment| (Uid::generate(), comment.build()))
+            .collect();
         let variable_ctx: HashMap<String, Uid> = variables
             .iter()
             .map(|(uid, var)| (var.name.clone(), Uid::new(uid)))
@@ -197,7 +304,7 @@ impl TargetBuilder {
             current_costume: current_costume as i64,
             costumes,
             sounds,
-            layer_order: layer_order as i64,
+            layer_order: layer_order.unwrap_or_default() as i64,
             volume: volume.into(),
         };
         (
@@ -222,13 +329,14 @@ impl Default for TargetBuilder {
             lists:           HashMap::default(),
             broadcasts:      HashMap::default(),
             block_stackes:   Vec::default(),
-            comments:        HashMap::default(),
+            comments:        Vec::default(),
             costumes:        Vec::default(),
             sounds:          Vec::default(),
             current_costume: 0,
-            layer_order:     0,
+            layer_order:     None,
             volume:          100.,
             custom_blocks:   Vec::default(),
+            layout:          LayoutOptions::default(),
         }
     }
 }
@@ -238,9 +346,11 @@ impl Default for TargetBuilder {
 pub struct StageBuilder {
     pub target:                  TargetBuilder,
     pub tempo:                   i64,
+    /// Camera state when the project loads. See [`crate::blocks::video_sensing`].
+    ///
+    /// Default to [`VideoState::Off`] so the project doesn't ask for camera permission.
     pub video_state:             VideoState,
     pub video_transparency:      i64,
-    custom_blocks:               HashMap<String, CustomBlockTy>,
     // Not availiable yet.
     // TODO: do this.
     // text_to_speech_language: (),
@@ -302,9 +412,8 @@ impl Default for StageBuilder {
                 ..Default::default()
             },
             tempo: 60,
-            video_state: VideoState::On,
+            video_state: VideoState::Off,
             video_transparency: 50,
-            custom_blocks: HashMap::default(),
         }
     }
 }
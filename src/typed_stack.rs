@@ -0,0 +1,271 @@
+//! Stacks that knows their shape
+//!
+//! [`StackBuilder`] lets anything be stacked on anything.
+//! [`TypedStack`] keeps the shape in its type so that continuing after a cap block or a reporter,
+//! or putting a round reporter in a boolean input, doesn't compile.
+//!
+//! ```
+//! # use sb_itchy::{blocks, typed_stack::{shape::*, TypedStack}};
+//! let script = TypedStack::<Script>::try_from(blocks::when_flag_clicked()).unwrap();
+//! let hide = TypedStack::<Command>::try_from(blocks::hide()).unwrap();
+//! let clicked = TypedStack::<Boolean>::try_from(blocks::mouse_down()).unwrap();
+//! let script = script.next(sb_itchy::typed_stack::if_(clicked, hide));
+//! ```
+//!
+//! ```compile_fail
+//! # use sb_itchy::{blocks, typed_stack::{shape::*, TypedStack}};
+//! let end = TypedStack::<Capped>::try_from(blocks::delete_this_clone()).unwrap();
+//! let hide = TypedStack::<Command>::try_from(blocks::hide()).unwrap();
+//! end.next(hide);
+//! ```
+//!
+//! Hats only go at the top, not under another block.
+//!
+//! ```compile_fail
+//! # use sb_itchy::{blocks, typed_stack::{shape::*, TypedStack}};
+//! let hide = TypedStack::<Command>::try_from(blocks::hide()).unwrap();
+//! let script = TypedStack::<Script>::try_from(blocks::when_flag_clicked()).unwrap();
+//! hide.next(script);
+//! ```
+//!
+//! ```compile_fail
+//! # use sb_itchy::{blocks, typed_stack::{shape::*, TypedStack}};
+//! let script = TypedStack::<Script>::try_from(blocks::when_flag_clicked()).unwrap();
+//! let clicked = TypedStack::<Script>::try_from(blocks::when_this_sprite_clicked()).unwrap();
+//! script.next(clicked);
+//! ```
+
+use std::marker::PhantomData;
+
+use crate::{
+    block::{BlockBuilder, BlockInputBuilder},
+    blocks,
+    registry::BlockShape,
+    stack::StackBuilder,
+};
+
+/// Marker types for [`TypedStack`]
+pub mod shape {
+    /// Starts with a hat block and can be continued
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Script;
+    /// Starts with a hat block and ends with a cap block
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct CappedScript;
+    /// Can be put under a block and can be continued
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Command;
+    /// Can be put under a block and ends with a cap block like `forever` or `delete this clone`
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Capped;
+    /// Round reporter
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Reporter;
+    /// Pointy reporter
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Boolean;
+}
+
+use shape::*;
+
+/// Shapes that [`TypedStack::try_from`] can check
+pub trait Shape {
+    fn matches(stack: &StackBuilder) -> bool;
+}
+
+/// `Self` followed by `Next`
+pub trait Then<Next> {
+    type Output;
+}
+
+/// Can be put in a C-block
+pub trait Body {}
+
+impl Then<Command> for Script {
+    type Output = Script;
+}
+impl Then<Capped> for Script {
+    type Output = CappedScript;
+}
+impl Then<Command> for Command {
+    type Output = Command;
+}
+impl Then<Capped> for Command {
+    type Output = Capped;
+}
+
+impl Body for Command {}
+impl Body for Capped {}
+
+impl Shape for Script {
+    fn matches(stack: &StackBuilder) -> bool {
+        is_sequence(stack, true, false)
+    }
+}
+impl Shape for CappedScript {
+    fn matches(stack: &StackBuilder) -> bool {
+        is_sequence(stack, true, true)
+    }
+}
+impl Shape for Command {
+    fn matches(stack: &StackBuilder) -> bool {
+        is_sequence(stack, false, false)
+    }
+}
+impl Shape for Capped {
+    fn matches(stack: &StackBuilder) -> bool {
+        is_sequence(stack, false, true)
+    }
+}
+impl Shape for Reporter {
+    fn matches(stack: &StackBuilder) -> bool {
+        is_single(stack, BlockShape::Reporter)
+    }
+}
+impl Shape for Boolean {
+    fn matches(stack: &StackBuilder) -> bool {
+        is_single(stack, BlockShape::Boolean)
+    }
+}
+
+/// [`StackBuilder`] with its shape in the type, see the [module](self) docs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedStack<S> {
+    stack: StackBuilder,
+    _shape: PhantomData<S>,
+}
+
+impl<S> TypedStack<S> {
+    /// Trust that `stack` has this shape.
+    /// For blocks that aren't in the [`registry`](crate::registry), like extension blocks.
+    pub fn from_unchecked(stack: StackBuilder) -> TypedStack<S> {
+        TypedStack {
+            stack,
+            _shape: PhantomData,
+        }
+    }
+
+    pub fn into_stack(self) -> StackBuilder {
+        self.stack
+    }
+
+    pub fn next<N>(self, next: TypedStack<N>) -> TypedStack<<S as Then<N>>::Output>
+    where
+        S: Then<N>,
+    {
+        TypedStack::from_unchecked(self.stack.next(next.stack))
+    }
+}
+
+impl<S: Shape> TryFrom<StackBuilder> for TypedStack<S> {
+    type Error = StackBuilder;
+
+    /// Gives back the stack when it doesn't have this shape.
+    fn try_from(stack: StackBuilder) -> Result<Self, Self::Error> {
+        if S::matches(&stack) {
+            Ok(TypedStack::from_unchecked(stack))
+        } else {
+            Err(stack)
+        }
+    }
+}
+
+impl<S> From<TypedStack<S>> for StackBuilder {
+    fn from(typed: TypedStack<S>) -> Self {
+        typed.stack
+    }
+}
+
+impl From<TypedStack<Reporter>> for BlockInputBuilder {
+    fn from(typed: TypedStack<Reporter>) -> Self {
+        BlockInputBuilder::stack(typed.stack)
+    }
+}
+
+impl From<TypedStack<Boolean>> for BlockInputBuilder {
+    fn from(typed: TypedStack<Boolean>) -> Self {
+        BlockInputBuilder::stack(typed.stack)
+    }
+}
+
+pub fn if_<B: Body>(condition: TypedStack<Boolean>, then: TypedStack<B>) -> TypedStack<Command> {
+    TypedStack::from_unchecked(blocks::if_(
+        condition.into(),
+        Some(BlockInputBuilder::stack(then.stack)),
+    ))
+}
+
+pub fn if_else<B: Body, C: Body>(
+    condition: TypedStack<Boolean>,
+    then: TypedStack<B>,
+    otherwise: TypedStack<C>,
+) -> TypedStack<Command> {
+    TypedStack::from_unchecked(blocks::if_else(
+        condition.into(),
+        Some(BlockInputBuilder::stack(then.stack)),
+        Some(BlockInputBuilder::stack(otherwise.stack)),
+    ))
+}
+
+pub fn repeat<B: Body>(times: BlockInputBuilder, body: TypedStack<B>) -> TypedStack<Command> {
+    TypedStack::from_unchecked(blocks::repeat(
+        times,
+        Some(BlockInputBuilder::stack(body.stack)),
+    ))
+}
+
+pub fn repeat_until<B: Body>(
+    condition: TypedStack<Boolean>,
+    body: TypedStack<B>,
+) -> TypedStack<Command> {
+    TypedStack::from_unchecked(blocks::repeat_until(
+        condition.into(),
+        Some(BlockInputBuilder::stack(body.stack)),
+    ))
+}
+
+pub fn wait_until(condition: TypedStack<Boolean>) -> TypedStack<Command> {
+    TypedStack::from_unchecked(blocks::wait_until(condition.into()))
+}
+
+/// Nothing can go under it.
+///
+/// ```compile_fail
+/// # use sb_itchy::{blocks, typed_stack::{self, shape::*, TypedStack}};
+/// let hide = TypedStack::<Command>::try_from(blocks::hide()).unwrap();
+/// let show = TypedStack::<Command>::try_from(blocks::show()).unwrap();
+/// typed_stack::forever(hide).next(show);
+/// ```
+pub fn forever<B: Body>(body: TypedStack<B>) -> TypedStack<Capped> {
+    TypedStack::from_unchecked(blocks::forever(Some(BlockInputBuilder::stack(body.stack))))
+}
+
+fn is_single(stack: &StackBuilder, shape: BlockShape) -> bool {
+    match stack.stack.as_slice() {
+        [block] => block.shape() == Some(shape),
+        _ => false,
+    }
+}
+
+/// Blocks that can be stacked, starting with a hat if `hat` and ending with a cap if `capped`
+fn is_sequence(stack: &StackBuilder, hat: bool, capped: bool) -> bool {
+    let Some(shapes) = stack
+        .stack
+        .iter()
+        .map(BlockBuilder::shape)
+        .collect::<Option<Vec<_>>>()
+    else {
+        return false;
+    };
+    let Some((last, rest)) = shapes.split_last() else {
+        return false;
+    };
+    let top = if hat {
+        shapes[0].is_hat()
+    } else {
+        shapes[0].has_previous()
+    };
+    top && shapes[1..].iter().all(|shape| shape.has_previous())
+        && rest.iter().all(|shape| shape.has_next())
+        && last.has_next() != capped
+}
@@ -0,0 +1,461 @@
+//! Checking blocks before building
+//!
+//! Nothing here is required to build a project, [`ProjectBuilder::build`] doesn't validate anything.
+//! Run a [`Validator`] over your project to catch mistakes that Scratch would otherwise silently accept or refuse to load,
+//! or build with [`ProjectBuilder::build_validated`] which refuses to build when there are errors.
+
+use crate::{
+    block::{BlockBuilder, BlockInputBuilder, BlockNormalBuilder, StackOrValue},
+    custom_block::SharedCustomBlock,
+    extension::{ExtensionDef, ExtensionInfo},
+    opcode::OpCode,
+    project::ProjectBuilder,
+    registry::{BlockShape, FieldMenu, InputKind, OpCodeInfo},
+    stack::StackBuilder,
+    target::TargetBuilder,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// Block is hidden from the editor's palette and [`Validator::allow_hidden_blocks`] is false
+    HiddenBlock {
+        opcode: String,
+    },
+    /// Opcode isn't in the [`registry`](crate::registry)
+    UnknownOpCode {
+        opcode: String,
+    },
+    /// Input that needs a value or a menu is missing
+    MissingInput {
+        opcode: String,
+        input: String,
+    },
+    /// Block doesn't have this input
+    UnknownInput {
+        opcode: String,
+        input: String,
+    },
+    MissingField {
+        opcode: String,
+        field: String,
+    },
+    /// Block doesn't have this field
+    UnknownField {
+        opcode: String,
+        field: String,
+    },
+    /// Field value isn't one of the fixed options of the field's menu
+    InvalidFieldOption {
+        opcode: String,
+        field: String,
+        value: String,
+    },
+    /// Menu input has a shadow block that isn't the menu this input expected
+    WrongMenu {
+        opcode: String,
+        input: String,
+        expected: String,
+        found: String,
+    },
+    /// Block put in an input doesn't fit in it, like a round reporter in a boolean input
+    /// or a reporter in a `SUBSTACK`
+    WrongInputShape {
+        opcode: String,
+        input: String,
+        expected: InputKind,
+        found: BlockShape,
+    },
+    /// Monitor is for a sprite that isn't in the project
+    UnknownSprite {
+        sprite: String,
+    },
+    /// Monitor is for a variable that the sprite, or the stage when `sprite` is [`None`], doesn't have
+    UnknownVariable {
+        sprite: Option<String>,
+        name: String,
+    },
+    /// Monitor is for a list that the sprite, or the stage when `sprite` is [`None`], doesn't have
+    UnknownList {
+        sprite: Option<String>,
+        name: String,
+    },
+    /// Monitor of a sprite-specific reporter like `x position` doesn't say which sprite
+    MonitorWithoutSprite {
+        opcode: String,
+    },
+    /// Custom block is called or defined but the target doesn't have it,
+    /// nor does the project's library when validating a project
+    UnknownCustomBlock {
+        proccode: String,
+    },
+}
+
+/// How bad a [`ValidationError`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Scratch loads and runs it but it isn't something the editor lets you make
+    Warning,
+    Error,
+}
+
+impl ValidationError {
+    pub fn severity(&self) -> Severity {
+        match self {
+            // The editor lets you drop a boolean in a round input
+            ValidationError::WrongInputShape {
+                expected,
+                found: BlockShape::Boolean,
+                ..
+            } if !matches!(expected, InputKind::Boolean | InputKind::SubStack) => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::HiddenBlock { opcode } => {
+                write!(
+                    f,
+                    "`{opcode}` is a hidden block and hidden blocks are not allowed"
+                )
+            }
+            ValidationError::UnknownOpCode { opcode } => write!(f, "unknown opcode `{opcode}`"),
+            ValidationError::MissingInput { opcode, input } => {
+                write!(f, "`{opcode}` is missing input `{input}`")
+            }
+            ValidationError::UnknownInput { opcode, input } => {
+                write!(f, "`{opcode}` doesn't have input `{input}`")
+            }
+            ValidationError::MissingField { opcode, field } => {
+                write!(f, "`{opcode}` is missing field `{field}`")
+            }
+            ValidationError::UnknownField { opcode, field } => {
+                write!(f, "`{opcode}` doesn't have field `{field}`")
+            }
+            ValidationError::InvalidFieldOption {
+                opcode,
+                field,
+                value,
+            } => write!(
+                f,
+                "`{value}` is not an option of field `{field}` in `{opcode}`"
+            ),
+            ValidationError::WrongMenu {
+                opcode,
+                input,
+                expected,
+                found,
+            } => write!(
+                f,
+                "input `{input}` of `{opcode}` expected menu `{expected}` but found `{found}`"
+            ),
+            ValidationError::WrongInputShape {
+                opcode,
+                input,
+                expected,
+                found,
+            } => write!(
+                f,
+                "input `{input}` of `{opcode}` is a {expected:?} input but has a {found:?} block in it"
+            ),
+            ValidationError::UnknownSprite { sprite } => {
+                write!(f, "monitor refers to sprite `{sprite}` that doesn't exist")
+            }
+            ValidationError::UnknownVariable { sprite, name } => match sprite {
+                Some(sprite) => write!(f, "sprite `{sprite}` doesn't have variable `{name}`"),
+                None => write!(f, "stage doesn't have variable `{name}`"),
+            },
+            ValidationError::UnknownList { sprite, name } => match sprite {
+                Some(sprite) => write!(f, "sprite `{sprite}` doesn't have list `{name}`"),
+                None => write!(f, "stage doesn't have list `{name}`"),
+            },
+            ValidationError::MonitorWithoutSprite { opcode } => {
+                write!(f, "monitor of `{opcode}` needs a sprite")
+            }
+            ValidationError::UnknownCustomBlock { proccode } => {
+                write!(f, "custom block `{proccode}` isn't in the target")
+            }
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Validator {
+    /// Hidden blocks still run in the VM but can't be dragged out of the editor's palette.
+    /// See [`StandardOpCode::HIDDEN`](crate::opcode::StandardOpCode::HIDDEN).
+    pub allow_hidden_blocks: bool,
+    /// Blocks of these extensions are checked like the blocks in the [`registry`](crate::registry).
+    /// Extensions added to the project are always included in [`Validator::validate_project`].
+    pub extensions:          Vec<ExtensionInfo>,
+}
+
+impl Validator {
+    pub fn new() -> Validator {
+        Validator::default()
+    }
+
+    pub fn set_allow_hidden_blocks(&mut self, allow_hidden_blocks: bool) -> &mut Self {
+        self.allow_hidden_blocks = allow_hidden_blocks;
+        self
+    }
+
+    pub fn add_extension<E: ExtensionDef>(&mut self, extension: &E) -> &mut Self {
+        self.extensions.push(extension.info());
+        self
+    }
+
+    pub fn validate_project(&self, project: &ProjectBuilder) -> Vec<ValidationError> {
+        let mut validator = self.clone();
+        validator
+            .extensions
+            .extend(project.extensions.iter().copied());
+        let mut errors = vec![];
+        for target in std::iter::once(&project.stage_builder.target)
+            .chain(project.sprite_builders.iter().map(|sprite| &sprite.target))
+        {
+            errors.extend(validator.validate_target_with(target, &project.custom_blocks));
+        }
+        errors.extend(
+            project
+                .monitors
+                .iter()
+                .filter_map(|monitor| monitor.validate(project)),
+        );
+        errors
+    }
+
+    pub fn validate_target(&self, target: &TargetBuilder) -> Vec<ValidationError> {
+        self.validate_target_with(target, &[])
+    }
+
+    /// Custom blocks in `library` count as ones the target has
+    fn validate_target_with(
+        &self,
+        target: &TargetBuilder,
+        library: &[SharedCustomBlock],
+    ) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        for stack in &target.block_stackes {
+            errors.extend(self.validate_stack(stack));
+        }
+
+        let known = |proccode: &str| {
+            target
+                .custom_blocks
+                .iter()
+                .any(|ty| ty.proccode() == proccode)
+                || library
+                    .iter()
+                    .any(|shared| shared.signature.proccode() == proccode)
+        };
+        let mut unknown: Vec<String> = vec![];
+        for stack in &target.block_stackes {
+            stack.walk(&mut |block| {
+                let proccode = match block {
+                    BlockBuilder::CustomBlock(c) => c.proccode(),
+                    BlockBuilder::CustomBlockCall(c) => c.proccode(),
+                    BlockBuilder::Normal(_) | BlockBuilder::VarList(_) => return,
+                };
+                if !known(proccode) && !unknown.iter().any(|name| name == proccode) {
+                    unknown.push(proccode.to_owned());
+                }
+            });
+        }
+        errors.extend(
+            unknown
+                .into_iter()
+                .map(|proccode| ValidationError::UnknownCustomBlock { proccode }),
+        );
+        errors
+    }
+
+    pub fn validate_stack(&self, stack: &StackBuilder) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        self.validate_stack_into(stack, &mut errors);
+        errors
+    }
+
+    pub fn validate_block(&self, block: &BlockNormalBuilder) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        self.validate_block_into(block, &mut errors);
+        errors
+    }
+
+    fn validate_stack_into(&self, stack: &StackBuilder, errors: &mut Vec<ValidationError>) {
+        for block in &stack.stack {
+            match block {
+                BlockBuilder::Normal(n) => self.validate_block_into(n, errors),
+                BlockBuilder::CustomBlockCall(c) => {
+                    for (_, input) in &c.args {
+                        self.validate_input_stacks(input.values.iter(), errors);
+                    }
+                }
+                BlockBuilder::CustomBlock(_) | BlockBuilder::VarList(_) => {}
+            }
+        }
+    }
+
+    fn validate_block_into(&self, block: &BlockNormalBuilder, errors: &mut Vec<ValidationError>) {
+        let opcode = block.opcode();
+        if !self.allow_hidden_blocks
+            && matches!(opcode, OpCode::Standard(standard) if standard.is_hidden())
+        {
+            errors.push(ValidationError::HiddenBlock {
+                opcode: opcode.to_string(),
+            });
+        }
+
+        match self.info(opcode) {
+            Some(info) if !info.is_dynamic() => self.validate_schema(block, info, errors),
+            Some(_) => {}
+            None => errors.push(ValidationError::UnknownOpCode {
+                opcode: opcode.to_string(),
+            }),
+        }
+
+        for input in block.inputs().values() {
+            self.validate_input_stacks(input.values.iter(), errors);
+        }
+    }
+
+    /// Check inputs and fields against the block's [`OpCodeInfo`]
+    fn validate_schema(
+        &self,
+        block: &BlockNormalBuilder,
+        info: &OpCodeInfo,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let opcode = info.opcode.to_owned();
+
+        for input_info in info.inputs {
+            match block.inputs().get(input_info.name) {
+                Some(input) => {
+                    if let Some(found) = self.misplaced_shape(input, input_info.kind) {
+                        errors.push(ValidationError::WrongInputShape {
+                            opcode: opcode.clone(),
+                            input: input_info.name.to_owned(),
+                            expected: input_info.kind,
+                            found,
+                        });
+                    }
+                    if let InputKind::Menu(menu_opcode) = input_info.kind {
+                        if let Some(found) = shadow_opcode(input) {
+                            if found != menu_opcode {
+                                errors.push(ValidationError::WrongMenu {
+                                    opcode: opcode.clone(),
+                                    input: input_info.name.to_owned(),
+                                    expected: menu_opcode.to_owned(),
+                                    found,
+                                });
+                            }
+                        }
+                    }
+                }
+                None => {
+                    if !matches!(input_info.kind, InputKind::Boolean | InputKind::SubStack) {
+                        errors.push(ValidationError::MissingInput {
+                            opcode: opcode.clone(),
+                            input: input_info.name.to_owned(),
+                        });
+                    }
+                }
+            }
+        }
+        for input in block.inputs().keys() {
+            if info.input(input).is_none() {
+                errors.push(ValidationError::UnknownInput {
+                    opcode: opcode.clone(),
+                    input: input.clone(),
+                });
+            }
+        }
+
+        for field_info in info.fields {
+            match block.fields().get(field_info.name) {
+                Some(field) => {
+                    if let FieldMenu::Options(options) = field_info.menu {
+                        if !options.contains(&field.value.as_str()) {
+                            errors.push(ValidationError::InvalidFieldOption {
+                                opcode: opcode.clone(),
+                                field: field_info.name.to_owned(),
+                                value: field.value.clone(),
+                            });
+                        }
+                    }
+                }
+                None => errors.push(ValidationError::MissingField {
+                    opcode: opcode.clone(),
+                    field: field_info.name.to_owned(),
+                }),
+            }
+        }
+        for field in block.fields().keys() {
+            if info.field(field).is_none() {
+                errors.push(ValidationError::UnknownField {
+                    opcode: opcode.clone(),
+                    field: field.clone(),
+                });
+            }
+        }
+    }
+
+    /// Shape of the block in `input` if it doesn't fit in an input of this kind
+    fn misplaced_shape(&self, input: &BlockInputBuilder, kind: InputKind) -> Option<BlockShape> {
+        input.values.iter().find_map(|value| {
+            let Some(StackOrValue::Stack(stack)) = value else {
+                return None;
+            };
+            let shape = match stack.stack.first()? {
+                BlockBuilder::Normal(n) if n.is_shadow() => return None,
+                BlockBuilder::Normal(n) => match n.shape() {
+                    Some(shape) => shape,
+                    None => self.info(n.opcode())?.shape,
+                },
+                block => block.shape()?,
+            };
+            let fits = match kind {
+                InputKind::Boolean => shape == BlockShape::Boolean,
+                InputKind::SubStack => shape.has_previous(),
+                _ => shape == BlockShape::Reporter,
+            };
+            (!fits).then_some(shape)
+        })
+    }
+
+    /// Block's [`OpCodeInfo`] from the [`registry`](crate::registry) or one of the extensions
+    fn info(&self, opcode: &OpCode) -> Option<&'static OpCodeInfo> {
+        opcode.info().or_else(|| {
+            self.extensions
+                .iter()
+                .find_map(|extension| extension.block(opcode.as_str()))
+        })
+    }
+
+    fn validate_input_stacks<'a, I: Iterator<Item = &'a Option<StackOrValue>>>(
+        &self,
+        values: I,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        for value in values {
+            if let Some(StackOrValue::Stack(stack)) = value {
+                self.validate_stack_into(stack, errors);
+            }
+        }
+    }
+}
+
+/// Opcode of the shadow block in this input, if there is one.
+fn shadow_opcode(input: &BlockInputBuilder) -> Option<String> {
+    input.values.iter().find_map(|value| match value {
+        Some(StackOrValue::Stack(stack)) => match stack.stack.first() {
+            Some(BlockBuilder::Normal(n)) if n.is_shadow() => Some(n.opcode().to_string()),
+            _ => None,
+        },
+        _ => None,
+    })
+}
@@ -0,0 +1,687 @@
+//! Every constructor in `blocks` should produce a block that matches the opcode registry.
+
+use sb_itchy::{
+    blocks::{self, video_sensing},
+    prelude::*,
+    registry::{self, BlockShape, InputKind},
+};
+
+type Bib = BlockInputBuilder;
+type Bfb = BlockFieldBuilder;
+type Biv = BlockInputValue;
+
+fn num() -> Bib {
+    Bib::value(Biv::Number { value: 10.into() })
+}
+
+fn text() -> Bib {
+    Bib::value(Biv::String {
+        value: "Hello!".to_owned().into(),
+    })
+}
+
+fn color() -> Bib {
+    Bib::value(Biv::Color {
+        value: "#ff0000".to_owned().into(),
+    })
+}
+
+fn field(value: &str) -> Bfb {
+    Bfb::new(value.to_owned())
+}
+
+fn menu(stack: StackBuilder) -> Bib {
+    Bib::shadow_stack(stack)
+}
+
+fn cond() -> Bib {
+    Bib::stack(blocks::mouse_down())
+}
+
+fn body() -> Option<Bib> {
+    Some(Bib::stack(blocks::show()))
+}
+
+fn check(stack: StackBuilder, opcode: &str) {
+    let Some(BlockBuilder::Normal(block)) = stack.stack.first() else {
+        panic!("`{opcode}` is not a normal block");
+    };
+    assert_eq!(block.opcode(), opcode);
+    let mut validator = Validator::new();
+    validator.set_allow_hidden_blocks(true);
+    let errors = validator.validate_stack(&stack);
+    assert!(errors.is_empty(), "`{opcode}`: {errors:?}");
+}
+
+#[test]
+fn control() {
+    check(blocks::wait(num()), "control_wait");
+    check(blocks::repeat(num(), body()), "control_repeat");
+    check(blocks::forever(body()), "control_forever");
+    check(blocks::if_(cond(), body()), "control_if");
+    check(blocks::if_else(cond(), body(), body()), "control_if_else");
+    check(blocks::wait_until(cond()), "control_wait_until");
+    check(blocks::repeat_until(cond(), body()), "control_repeat_until");
+    check(blocks::stop(field("all"), false), "control_stop");
+    check(blocks::when_i_start_as_a_clone(), "control_start_as_clone");
+    check(
+        blocks::create_clone_of(menu(blocks::create_clone_of_menu(field("_myself_")))),
+        "control_create_clone_of",
+    );
+    check(
+        blocks::create_clone_of_menu(field("_myself_")),
+        "control_create_clone_of_menu",
+    );
+    check(blocks::delete_this_clone(), "control_delete_this_clone");
+    check(blocks::while_(cond(), body()), "control_while");
+    check(
+        blocks::for_each(field("i"), num(), body()),
+        "control_for_each",
+    );
+    check(blocks::get_counter(), "control_get_counter");
+    check(blocks::incr_counter(), "control_incr_counter");
+    check(blocks::clear_counter(), "control_clear_counter");
+    check(blocks::all_at_once(body()), "control_all_at_once");
+}
+
+#[test]
+fn event() {
+    check(blocks::when_flag_clicked(), "event_whenflagclicked");
+    check(
+        blocks::when_key_pressed(field("space")),
+        "event_whenkeypressed",
+    );
+    check(
+        blocks::when_this_sprite_clicked(),
+        "event_whenthisspriteclicked",
+    );
+    check(
+        blocks::when_backdrop_switches_to(field("backdrop1")),
+        "event_whenbackdropswitchesto",
+    );
+    check(
+        blocks::when_greater_than(field("TIMER"), num()),
+        "event_whengreaterthan",
+    );
+    check(
+        blocks::when_broadcast_received(field("message1")),
+        "event_whenbroadcastreceived",
+    );
+    let message = || {
+        Bib::value(Biv::Broadcast {
+            name: "message1".to_owned(),
+            id: "message1".to_owned(),
+        })
+    };
+    check(blocks::broadcast(message()), "event_broadcast");
+    check(
+        blocks::broadcast_and_wait(message()),
+        "event_broadcastandwait",
+    );
+}
+
+#[test]
+fn looks() {
+    check(blocks::think(text()), "looks_think");
+    check(blocks::think_for_secs(text(), num()), "looks_thinkforsecs");
+    check(blocks::say(text()), "looks_say");
+    check(blocks::say_for_secs(text(), num()), "looks_sayforsecs");
+    check(
+        blocks::switch_costume_to(menu(blocks::costume_menu(field("costume1")))),
+        "looks_switchcostumeto",
+    );
+    check(blocks::costume_menu(field("costume1")), "looks_costume");
+    check(blocks::next_costume(), "looks_nextcostume");
+    check(
+        blocks::switch_backdrop_to(menu(blocks::backdrop_menu(field("backdrop1")))),
+        "looks_switchbackdropto",
+    );
+    check(blocks::backdrop_menu(field("backdrop1")), "looks_backdrops");
+    check(blocks::next_backdrop(), "looks_nextbackdrop");
+    check(blocks::change_size_by(num()), "looks_changesizeby");
+    check(blocks::set_size_to(num()), "looks_setsizeto");
+    check(
+        blocks::change_looks_effect_by(field("COLOR"), num()),
+        "looks_changeeffectby",
+    );
+    check(
+        blocks::set_looks_effect_to(field("GHOST"), num()),
+        "looks_seteffectto",
+    );
+    check(blocks::clear_graphic_effects(), "looks_cleargraphiceffects");
+    check(blocks::show(), "looks_show");
+    check(blocks::hide(), "looks_hide");
+    check(blocks::hide_all_sprites(), "looks_hideallsprites");
+    check(blocks::go_to_layer(field("front")), "looks_gotofrontback");
+    check(
+        blocks::change_layer(field("forward"), num()),
+        "looks_goforwardbackwardlayers",
+    );
+    check(blocks::costume(field("number")), "looks_costumenumbername");
+    check(blocks::backdrop(field("name")), "looks_backdropnumbername");
+    check(blocks::size(), "looks_size");
+}
+
+#[test]
+fn motion() {
+    check(blocks::move_steps(num()), "motion_movesteps");
+    check(blocks::turn_right(num()), "motion_turnright");
+    check(blocks::turn_left(num()), "motion_turnleft");
+    check(
+        blocks::go_to(menu(blocks::go_to_menu(field("_random_")))),
+        "motion_goto",
+    );
+    check(blocks::go_to_menu(field("_random_")), "motion_goto_menu");
+    check(blocks::goto_xy(num(), num()), "motion_gotoxy");
+    check(
+        blocks::glide_to(num(), menu(blocks::glide_to_menu(field("_mouse_")))),
+        "motion_glideto",
+    );
+    check(
+        blocks::glide_to_menu(field("_mouse_")),
+        "motion_glideto_menu",
+    );
+    check(
+        blocks::glide_to_xy(num(), num(), num()),
+        "motion_glidesecstoxy",
+    );
+    check(blocks::point_in_direction(num()), "motion_pointindirection");
+    check(
+        blocks::point_towards(menu(blocks::point_towards_menu(field("_mouse_")))),
+        "motion_pointtowards",
+    );
+    check(
+        blocks::point_towards_menu(field("_mouse_")),
+        "motion_pointtowards_menu",
+    );
+    check(blocks::set_x(num()), "motion_setx");
+    check(blocks::set_y(num()), "motion_sety");
+    check(blocks::change_x_by(num()), "motion_changexby");
+    check(blocks::change_y_by(num()), "motion_changeyby");
+    check(blocks::if_on_edge_bounce(), "motion_ifonedgebounce");
+    check(
+        blocks::set_rotation_style(field("left-right")),
+        "motion_setrotationstyle",
+    );
+    check(blocks::x_position(), "motion_xposition");
+    check(blocks::y_position(), "motion_yposition");
+    check(blocks::direction(), "motion_direction");
+}
+
+#[test]
+fn operators() {
+    check(blocks::add(num(), num()), "operator_add");
+    check(blocks::sub(num(), num()), "operator_subtract");
+    check(blocks::mul(num(), num()), "operator_multiply");
+    check(blocks::div(num(), num()), "operator_divide");
+    check(blocks::random(num(), num()), "operator_random");
+    check(blocks::less_than(text(), text()), "operator_lt");
+    check(blocks::greater_than(text(), text()), "operator_gt");
+    check(blocks::equals(text(), text()), "operator_equals");
+    check(blocks::and(cond(), cond()), "operator_and");
+    check(blocks::or(cond(), cond()), "operator_or");
+    check(blocks::not(cond()), "operator_not");
+    check(blocks::join(text(), text()), "operator_join");
+    check(blocks::letter_of(num(), text()), "operator_letter_of");
+    check(blocks::length_of(text()), "operator_length");
+    check(blocks::contains(text(), text()), "operator_contains");
+    check(blocks::modulo(num(), num()), "operator_mod");
+    check(blocks::round(num()), "operator_round");
+    check(blocks::math_op(field("sqrt"), num()), "operator_mathop");
+}
+
+#[test]
+fn sensing() {
+    check(
+        blocks::touching(menu(blocks::touching_menu(field("_edge_")))),
+        "sensing_touchingobject",
+    );
+    check(
+        blocks::touching_menu(field("_edge_")),
+        "sensing_touchingobjectmenu",
+    );
+    check(blocks::touching_color(color()), "sensing_touchingcolor");
+    check(
+        blocks::color_touching_color(color(), color()),
+        "sensing_coloristouchingcolor",
+    );
+    check(
+        blocks::distance_to(menu(blocks::distance_to_menu(field("_mouse_")))),
+        "sensing_distanceto",
+    );
+    check(
+        blocks::distance_to_menu(field("_mouse_")),
+        "sensing_distancetomenu",
+    );
+    check(blocks::ask_and_wait(text()), "sensing_askandwait");
+    check(blocks::answer(), "sensing_answer");
+    check(
+        blocks::key_pressed(menu(blocks::key_menu(field("space")))),
+        "sensing_keypressed",
+    );
+    check(blocks::key_menu(field("space")), "sensing_keyoptions");
+    check(blocks::mouse_down(), "sensing_mousedown");
+    check(blocks::mouse_x(), "sensing_mousex");
+    check(blocks::mouse_y(), "sensing_mousey");
+    check(
+        blocks::set_drag_mode(field("draggable")),
+        "sensing_setdragmode",
+    );
+    check(blocks::loudness(), "sensing_loudness");
+    check(blocks::loud(), "sensing_loud");
+    check(blocks::timer(), "sensing_timer");
+    check(blocks::reset_timer(), "sensing_resettimer");
+    check(
+        blocks::var_of(
+            field("x position"),
+            menu(blocks::var_of_object_menu(field("Sprite1"))),
+        ),
+        "sensing_of",
+    );
+    check(
+        blocks::var_of_object_menu(field("_stage_")),
+        "sensing_of_object_menu",
+    );
+    check(blocks::current_datetime(field("YEAR")), "sensing_current");
+    check(blocks::days_since_2000(), "sensing_dayssince2000");
+    check(blocks::username(), "sensing_username");
+    check(blocks::user_id(), "sensing_userid");
+}
+
+#[test]
+fn sound() {
+    check(
+        blocks::play_sound_until_done(menu(blocks::sound_menu(field("Meow")))),
+        "sound_playuntildone",
+    );
+    check(
+        blocks::play_sound(menu(blocks::sound_menu(field("Meow")))),
+        "sound_play",
+    );
+    check(blocks::sound_menu(field("Meow")), "sound_sounds_menu");
+    check(blocks::stop_all_sound(), "sound_stopallsounds");
+    check(
+        blocks::change_sound_effect_by(field("PITCH"), num()),
+        "sound_changeeffectby",
+    );
+    check(
+        blocks::set_sound_effect_to(field("PAN"), num()),
+        "sound_seteffectto",
+    );
+    check(blocks::clear_sound_effects(), "sound_cleareffects");
+    check(blocks::set_volume_to(num()), "sound_setvolumeto");
+    check(blocks::change_volume_by(num()), "sound_changevolumeby");
+    check(blocks::volume(), "sound_volume");
+}
+
+#[test]
+fn data() {
+    for stack in [
+        blocks::sprite_var("my variable"),
+        blocks::sprite_list("my list"),
+        blocks::global_var("my variable"),
+        blocks::global_list("my list"),
+    ] {
+        assert!(matches!(stack.stack.as_slice(), [BlockBuilder::VarList(_)]));
+        assert!(Validator::new().validate_stack(&stack).is_empty());
+    }
+    check(blocks::list_contents(field("my list")), "data_listcontents");
+    check(
+        blocks::set_var_to(field("my variable"), text()),
+        "data_setvariableto",
+    );
+    check(
+        blocks::change_var_by(field("my variable"), num()),
+        "data_changevariableby",
+    );
+    check(blocks::show_var(field("my variable")), "data_showvariable");
+    check(blocks::hide_var(field("my variable")), "data_hidevariable");
+    check(
+        blocks::add_to_list(field("my list"), text()),
+        "data_addtolist",
+    );
+    check(
+        blocks::delete_in_list(field("my list"), num()),
+        "data_deleteoflist",
+    );
+    check(
+        blocks::delete_all_in_list(field("my list")),
+        "data_deletealloflist",
+    );
+    check(
+        blocks::insert_in_list(field("my list"), num(), text()),
+        "data_insertatlist",
+    );
+    check(
+        blocks::replace_in_list(field("my list"), num(), text()),
+        "data_replaceitemoflist",
+    );
+    check(
+        blocks::item_in_list(field("my list"), num()),
+        "data_itemoflist",
+    );
+    check(
+        blocks::count_of_item_in_list(field("my list"), text()),
+        "data_itemnumoflist",
+    );
+    check(
+        blocks::length_of_list(field("my list")),
+        "data_lengthoflist",
+    );
+    check(
+        blocks::list_contains(field("my list"), text()),
+        "data_listcontainsitem",
+    );
+    check(blocks::show_list(field("my list")), "data_showlist");
+    check(blocks::hide_list(field("my list")), "data_hidelist");
+}
+
+#[test]
+fn my_blocks() {
+    check(
+        blocks::custom_block_var_boolean("flag"),
+        "argument_reporter_boolean",
+    );
+    check(
+        blocks::custom_block_var_string_number("amount"),
+        "argument_reporter_string_number",
+    );
+}
+
+#[test]
+fn extensions() {
+    check(blocks::translate_to(text(), "ja"), "translate_getTranslate");
+    check(blocks::get_viewer_language(), "translate_getViewerLanguage");
+
+    check(
+        video_sensing::when_motion_greater_than(num()),
+        "videoSensing_whenMotionGreaterThan",
+    );
+    check(
+        video_sensing::video_on(
+            menu(video_sensing::video_attribute_menu(field("motion"))),
+            menu(video_sensing::video_subject_menu(field("this sprite"))),
+        ),
+        "videoSensing_videoOn",
+    );
+    check(
+        video_sensing::video_attribute_menu(field("direction")),
+        "videoSensing_menu_ATTRIBUTE",
+    );
+    check(
+        video_sensing::video_subject_menu(field("Stage")),
+        "videoSensing_menu_SUBJECT",
+    );
+    // Scratch only senses the stage with these exact names
+    for wrong in ["sprite", "stage"] {
+        let errors =
+            Validator::new().validate_stack(&video_sensing::video_subject_menu(field(wrong)));
+        assert_eq!(
+            errors,
+            [ValidationError::InvalidFieldOption {
+                opcode: "videoSensing_menu_SUBJECT".to_owned(),
+                field: "SUBJECT".to_owned(),
+                value: wrong.to_owned(),
+            }]
+        );
+    }
+    let Some(BlockBuilder::Normal(subject)) = video_sensing::video_subject_menu(None).stack.pop()
+    else {
+        unreachable!()
+    };
+    assert_eq!(subject.fields()["SUBJECT"].value, "this sprite");
+    check(
+        video_sensing::video_toggle(menu(video_sensing::video_state_menu(field("off")))),
+        "videoSensing_videoToggle",
+    );
+    check(
+        video_sensing::video_state_menu(field("on-flipped")),
+        "videoSensing_menu_VIDEO_STATE",
+    );
+    check(
+        video_sensing::set_video_transparency(num()),
+        "videoSensing_setVideoTransparency",
+    );
+}
+
+#[test]
+fn catches_mistakes() {
+    let validator = Validator::new();
+
+    assert_eq!(
+        validator.validate_stack(&blocks::get_counter()),
+        vec![ValidationError::HiddenBlock {
+            opcode: "control_get_counter".to_owned()
+        }]
+    );
+
+    assert_eq!(
+        validator.validate_stack(&blocks::change_layer(field("foward"), num())),
+        vec![ValidationError::InvalidFieldOption {
+            opcode: "looks_goforwardbackwardlayers".to_owned(),
+            field: "FORWARD_BACKWARD".to_owned(),
+            value: "foward".to_owned(),
+        }]
+    );
+
+    assert_eq!(
+        validator.validate_stack(&blocks::go_to(menu(blocks::glide_to_menu(field(
+            "_random_"
+        ))))),
+        vec![ValidationError::WrongMenu {
+            opcode: "motion_goto".to_owned(),
+            input: "TO".to_owned(),
+            expected: "motion_goto_menu".to_owned(),
+            found: "motion_glideto_menu".to_owned(),
+        }]
+    );
+
+    assert_eq!(
+        validator.validate_stack(&blocks::if_(Bib::stack(blocks::mouse_x()), body())),
+        vec![ValidationError::WrongInputShape {
+            opcode: "control_if".to_owned(),
+            input: "CONDITION".to_owned(),
+            expected: InputKind::Boolean,
+            found: BlockShape::Reporter,
+        }]
+    );
+    let boolean_in_number = validator.validate_stack(&blocks::move_steps(cond()));
+    assert_eq!(boolean_in_number.len(), 1);
+    assert_eq!(boolean_in_number[0].severity(), Severity::Warning);
+
+    let mut key_menu_with_input = BlockNormalBuilder::new(StandardOpCode::sensing_keyoptions);
+    key_menu_with_input
+        .add_input("KEY_OPTION", text())
+        .set_shadow(true);
+    assert_eq!(
+        validator.validate_block(&key_menu_with_input),
+        vec![
+            ValidationError::UnknownInput {
+                opcode: "sensing_keyoptions".to_owned(),
+                input: "KEY_OPTION".to_owned(),
+            },
+            ValidationError::MissingField {
+                opcode: "sensing_keyoptions".to_owned(),
+                field: "KEY_OPTION".to_owned(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn reporter_keeps_shadow() {
+    let mut sprite = SpriteBuilder::default();
+    sprite
+        .target
+        .add_block_stack(blocks::move_steps(Bib::stack(blocks::mouse_x())))
+        .add_block_stack(blocks::go_to(Bib::stack(blocks::mouse_y())))
+        .add_block_stack(blocks::if_(cond(), body()));
+    let mut project = ProjectBuilder::default();
+    project.add_sprite(sprite);
+    let project = project.build_json(&mut vec![]);
+    let blocks = project["targets"][1]["blocks"].as_object().unwrap();
+    let block = |opcode: &str| {
+        blocks
+            .values()
+            .find(|block| block["opcode"] == opcode)
+            .unwrap()
+    };
+
+    let steps = &block("motion_movesteps")["inputs"]["STEPS"];
+    assert_eq!(steps[0], 3);
+    assert_eq!(steps[2][0], 4);
+
+    let to = &block("motion_goto")["inputs"]["TO"];
+    assert_eq!(to[0], 3);
+    let menu = &blocks[to[2].as_str().unwrap()];
+    assert_eq!(menu["opcode"], "motion_goto_menu");
+    assert_eq!(menu["shadow"], true);
+    assert_eq!(menu["fields"]["TO"][0], "_random_");
+
+    let condition = &block("control_if")["inputs"]["CONDITION"];
+    assert_eq!(condition[0], 2);
+    assert_eq!(condition.as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn palette_defaults() {
+    let input = |stack: &StackBuilder, name: &str| {
+        let Some(BlockBuilder::Normal(block)) = stack.stack.first() else {
+            unreachable!()
+        };
+        block.inputs()[name].clone()
+    };
+
+    let steps = blocks::move_steps(None);
+    check(steps.clone(), "motion_movesteps");
+    assert_eq!(
+        input(&steps, "STEPS").values,
+        vec![Some(StackOrValue::Value(Biv::Number {
+            value: "10".to_owned().into()
+        }))]
+    );
+    assert_eq!(input(&blocks::move_steps(num()), "STEPS"), num());
+
+    let say = blocks::say_for_secs(text(), None);
+    check(say.clone(), "looks_sayforsecs");
+    assert_eq!(input(&say, "MESSAGE"), text());
+
+    let go_to = blocks::go_to(None);
+    check(go_to.clone(), "motion_goto");
+    assert_eq!(
+        input(&go_to, "TO"),
+        menu(blocks::go_to_menu(field("_random_")))
+    );
+
+    let toggle = video_sensing::video_toggle(None);
+    let Some(Some(StackOrValue::Stack(menu))) = input(&toggle, "VIDEO_STATE").values.pop() else {
+        panic!("no menu in `VIDEO_STATE`");
+    };
+    let Some(BlockBuilder::Normal(menu)) = menu.stack.first() else {
+        panic!("menu isn't a normal block");
+    };
+    assert_eq!(menu.fields()["VIDEO_STATE"].value, "on");
+}
+
+#[test]
+fn menus_default_to_the_palette() {
+    let field_of = |stack: &StackBuilder, name: &str| {
+        let Some(BlockBuilder::Normal(block)) = stack.stack.first() else {
+            unreachable!()
+        };
+        block.fields()[name].value.clone()
+    };
+
+    check(blocks::stop(None, false), "control_stop");
+    assert_eq!(field_of(&blocks::stop(None, false), "STOP_OPTION"), "all");
+    let layer = blocks::go_to_layer(None);
+    check(layer.clone(), "looks_gotofrontback");
+    assert_eq!(field_of(&layer, "FRONT_BACK"), "front");
+    let style = blocks::set_rotation_style(None);
+    check(style.clone(), "motion_setrotationstyle");
+    assert_eq!(field_of(&style, "STYLE"), "left-right");
+    assert_eq!(
+        field_of(&blocks::point_towards_menu(None), "TOWARDS"),
+        "_mouse_"
+    );
+    assert_eq!(
+        field_of(&blocks::when_backdrop_switches_to(None), "BACKDROP"),
+        "backdrop1"
+    );
+    assert_eq!(
+        field_of(&blocks::go_to_layer(field("back")), "FRONT_BACK"),
+        "back"
+    );
+}
+
+#[test]
+fn every_palette_default() {
+    let mut validator = Validator::new();
+    validator.set_allow_hidden_blocks(true);
+    for &(opcode, slot, value) in registry::palette_defaults() {
+        let info = registry::lookup(opcode).unwrap_or_else(|| panic!("no opcode `{opcode}`"));
+        let mut block = BlockNormalBuilder::new(opcode);
+        if info.input(slot).is_some() {
+            block.add_input_or_default(slot, None);
+            assert!(
+                block.inputs().contains_key(slot),
+                "`{opcode}` has no default for `{slot}`"
+            );
+        } else {
+            assert!(info.field(slot).is_some(), "`{opcode}` has no `{slot}`");
+            block.add_field_or_default(slot, None);
+            assert_eq!(block.fields()[slot].value, value);
+        }
+
+        let about_slot: Vec<ValidationError> = validator
+            .validate_block(&block)
+            .into_iter()
+            .filter(|error| match error {
+                ValidationError::UnknownInput { input, .. } => input == slot,
+                ValidationError::UnknownField { field, .. }
+                | ValidationError::InvalidFieldOption { field, .. } => field == slot,
+                _ => false,
+            })
+            .collect();
+        assert!(about_slot.is_empty(), "`{opcode}`: {about_slot:?}");
+    }
+}
+
+#[test]
+fn stop_shape() {
+    use sb_itchy::typed_stack::{shape::*, TypedStack};
+
+    let shape = |stack: &StackBuilder| stack.stack[0].shape();
+    assert_eq!(
+        shape(&blocks::stop(field("all"), false)),
+        Some(BlockShape::Cap)
+    );
+    assert_eq!(
+        shape(&blocks::stop(field("this script"), false)),
+        Some(BlockShape::Cap)
+    );
+    for option in ["other scripts in sprite", "other scripts in stage"] {
+        let stop = blocks::stop(field(option), true);
+        assert_eq!(shape(&stop), Some(BlockShape::Stack));
+        check(stop.clone(), "control_stop");
+        assert!(TypedStack::<Command>::try_from(stop.next(blocks::show())).is_ok());
+    }
+    assert!(TypedStack::<Capped>::try_from(blocks::stop(field("all"), false)).is_ok());
+}
+
+#[test]
+fn opcode_strings() {
+    for &opcode in StandardOpCode::ALL {
+        assert_eq!(OpCode::parse(opcode.as_str()), OpCode::Standard(opcode));
+        assert_eq!(opcode.as_str(), opcode.to_string());
+    }
+    for &opcode in PenExtensionOpCode::ALL {
+        assert_eq!(OpCode::parse(opcode.as_str()), OpCode::Pen(opcode));
+    }
+    for info in registry::all() {
+        assert_eq!(registry::lookup(info.opcode), Some(info));
+        assert!(OpCode::parse(info.opcode) == info.opcode);
+    }
+    assert_eq!(OpCode::parse("foo_bar").as_str(), "foo_bar");
+    assert!(registry::lookup("foo_bar").is_none());
+}
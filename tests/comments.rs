@@ -0,0 +1,186 @@
+//! Comments and the blocks they're attached to should point at each other in project.json.
+
+mod common;
+
+use common::{Bfb, Bib};
+use sb_itchy::{blocks, prelude::*, stack::BlockHeightData};
+use serde_json::Value as Json;
+
+fn with_comment(mut stack: StackBuilder, content: &str) -> StackBuilder {
+    match &mut stack.stack[0] {
+        BlockBuilder::Normal(n) => {
+            n.set_comment(Some(CommentBuilder::new(content)));
+        }
+        BlockBuilder::VarList(vl) => {
+            vl.set_comment(Some(CommentBuilder::new(content)));
+        }
+        _ => unreachable!(),
+    }
+    stack
+}
+
+fn build(sprite_stacks: Vec<StackBuilder>) -> Json {
+    let mut project = ProjectBuilder::default();
+    project.set_stage(common::stage());
+
+    let mut sprite = common::sprite();
+    for stack in sprite_stacks {
+        sprite.target.add_block_stack(stack);
+    }
+    project.add_sprite(sprite);
+
+    project.build_json(&mut vec![])
+}
+
+/// Every reference between blocks and comments exists and points back.
+fn check_links(project: &Json) -> usize {
+    let mut linked = 0;
+    for target in project["targets"].as_array().unwrap() {
+        let blocks = target["blocks"].as_object().unwrap();
+        let comments = target["comments"].as_object().unwrap();
+
+        for (comment_id, comment) in comments {
+            if let Some(block_id) = comment["blockId"].as_str() {
+                let block = blocks
+                    .get(block_id)
+                    .unwrap_or_else(|| panic!("comment `{comment_id}` is on a missing block"));
+                assert_eq!(block["comment"].as_str(), Some(comment_id.as_str()));
+                linked += 1;
+            }
+        }
+
+        for (block_id, block) in blocks {
+            if !block.is_object() {
+                continue;
+            }
+            if let Some(comment_id) = block["comment"].as_str() {
+                let comment = comments
+                    .get(comment_id)
+                    .unwrap_or_else(|| panic!("block `{block_id}` has a missing comment"));
+                assert_eq!(comment["blockId"].as_str(), Some(block_id.as_str()));
+            }
+            for relative in ["next", "parent"] {
+                if let Some(id) = block[relative].as_str() {
+                    assert!(
+                        blocks.contains_key(id),
+                        "`{relative}` of `{block_id}` is missing"
+                    );
+                }
+            }
+            for input in block["inputs"].as_object().unwrap().values() {
+                for value in input.as_array().unwrap().iter().skip(1) {
+                    if let Some(id) = value.as_str() {
+                        let child = blocks
+                            .get(id)
+                            .unwrap_or_else(|| panic!("input of `{block_id}` is missing `{id}`"));
+                        assert_eq!(child["parent"].as_str(), Some(block_id.as_str()));
+                    }
+                }
+            }
+        }
+    }
+    linked
+}
+
+fn sprite_blocks(project: &Json) -> Vec<Json> {
+    project["targets"][1]["blocks"]
+        .as_object()
+        .unwrap()
+        .values()
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn top_level_reporter() {
+    let project = build(vec![with_comment(
+        StackBuilder::start_varlist(BlockVarListBuilder::global_var("score")),
+        "the score",
+    )]);
+    assert_eq!(check_links(&project), 1);
+
+    let blocks = sprite_blocks(&project);
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0]["opcode"], "data_variable");
+    assert_eq!(blocks[0]["topLevel"], true);
+    assert_eq!(blocks[0]["fields"]["VARIABLE"][0], "score");
+}
+
+#[test]
+fn reporter_in_input() {
+    let project = build(vec![
+        blocks::say(Bib::stack(with_comment(
+            StackBuilder::start_varlist(BlockVarListBuilder::global_list("items")),
+            "all items",
+        ))),
+        blocks::say(Bib::stack(StackBuilder::start_varlist(
+            BlockVarListBuilder::global_var("score"),
+        ))),
+    ]);
+    assert_eq!(check_links(&project), 1);
+
+    let blocks = sprite_blocks(&project);
+    assert_eq!(blocks.len(), 3);
+    assert!(blocks
+        .iter()
+        .any(|block| block["opcode"] == "data_listcontents" && block["topLevel"] == false));
+}
+
+#[test]
+fn shadow_menu() {
+    let project = build(vec![blocks::go_to(Bib::shadow_stack(with_comment(
+        blocks::go_to_menu(Bfb::new("_random_".to_owned())),
+        "somewhere",
+    )))]);
+    assert_eq!(check_links(&project), 1);
+
+    let blocks = sprite_blocks(&project);
+    assert!(blocks
+        .iter()
+        .any(|block| block["opcode"] == "motion_goto_menu" && block["comment"].is_string()));
+}
+
+#[test]
+fn workspace_comment() {
+    let mut project = ProjectBuilder::default();
+    project
+        .stage_builder
+        .target
+        .add_comment(CommentBuilder::new("not on a block"));
+    let project = project.build_json(&mut vec![]);
+    assert_eq!(check_links(&project), 0);
+
+    let comments = project["targets"][0]["comments"].as_object().unwrap();
+    assert_eq!(comments.len(), 1);
+    let comment = comments.values().next().unwrap();
+    assert!(comment["blockId"].is_null());
+    assert!(comment["x"].is_number() && comment["y"].is_number());
+}
+
+#[test]
+fn else_comment_below_then() {
+    let then = (0..10).fold(with_comment(blocks::show(), "then"), |stack, _| {
+        stack.next(blocks::hide())
+    });
+    let project = build(vec![blocks::if_else(
+        Bib::stack(blocks::mouse_down()),
+        Some(Bib::stack(then.clone())),
+        Some(Bib::stack(with_comment(blocks::show(), "else"))),
+    )]);
+    assert_eq!(check_links(&project), 2);
+
+    let comments = project["targets"][1]["comments"].as_object().unwrap();
+    let y = |text: &str| {
+        comments
+            .values()
+            .find(|comment| comment["text"] == text)
+            .unwrap()["y"]
+            .as_f64()
+            .unwrap()
+    };
+    let data = BlockHeightData::default();
+    assert_eq!(
+        y("else") - y("then"),
+        then.calc_block_height(&data, false) + data.block_nest_height
+    );
+}
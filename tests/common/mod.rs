@@ -0,0 +1,459 @@
+//! Runs the blocks in a built project.json like Scratch would, for the blocks the tests need.
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use sb_itchy::{blocks, prelude::*};
+use sb_sbity::value::Value;
+use serde_json::{Map, Value as Json};
+
+pub type Bib = BlockInputBuilder;
+pub type Bfb = BlockFieldBuilder;
+
+pub fn num(value: f64) -> Bib {
+    Bib::value(BlockInputValue::Number {
+        value: value.into(),
+    })
+}
+
+/// Field pointing at the sprite's variable called `name`
+pub fn var(name: &str) -> Bfb {
+    Bfb::new_with_kind(name.to_owned(), FieldKind::SpriteVariable)
+}
+
+/// `sprite1`, the first target after the stage
+pub fn sprite() -> SpriteBuilder {
+    let mut sprite = SpriteBuilder::default();
+    sprite.target.set_name("sprite1").set_layer_order(1);
+    sprite
+}
+
+/// Stage with a `score` variable and an empty `items` list
+pub fn stage() -> StageBuilder {
+    let mut stage = StageBuilder::default();
+    stage
+        .target
+        .add_variable("score", VariableBuilder::new(Value::Number(0.into())))
+        .add_list("items", ListBuilder::new(vec![]));
+    stage
+}
+
+/// What `sprite` says when the green flag is clicked, with `script` under the green flag
+pub fn run(mut sprite: SpriteBuilder, script: StackBuilder) -> Vec<String> {
+    sprite
+        .target
+        .add_block_stack(blocks::when_flag_clicked().next(script));
+    let mut project = ProjectBuilder::default();
+    project.add_sprite(sprite);
+    let project = project.build_json(&mut vec![]);
+    Vm::new(&project, 1).run_green_flag().said.clone()
+}
+
+/// Id of the variable or list called `name` in the target's `kind` map
+pub fn id_of(target: &Json, kind: &str, name: &str) -> String {
+    target[kind]
+        .as_object()
+        .unwrap()
+        .iter()
+        .find(|(_, var)| var[0] == name)
+        .map(|(id, _)| id.clone())
+        .unwrap_or_else(|| panic!("no `{name}` in `{kind}`"))
+}
+
+/// Value of a variable, an item or an input
+#[derive(Debug, Clone, PartialEq)]
+pub enum V {
+    Num(f64),
+    Text(String),
+    Bool(bool),
+}
+
+impl V {
+    pub fn num(&self) -> f64 {
+        match self {
+            V::Num(n) => *n,
+            V::Text(s) => s.trim().parse().unwrap_or(0.),
+            V::Bool(b) => *b as u8 as f64,
+        }
+    }
+
+    pub fn truthy(&self) -> bool {
+        match self {
+            V::Num(n) => *n != 0. && !n.is_nan(),
+            V::Text(s) => !matches!(s.to_lowercase().as_str(), "" | "0" | "false"),
+            V::Bool(b) => *b,
+        }
+    }
+
+    /// As a number when it looks like one, Scratch compares those as numbers
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            V::Num(n) => Some(*n),
+            V::Bool(b) => Some(*b as u8 as f64),
+            V::Text(s) if s.trim().is_empty() => None,
+            V::Text(s) => s.trim().parse().ok(),
+        }
+    }
+
+    fn compare(&self, other: &V) -> std::cmp::Ordering {
+        match (self.as_number(), other.as_number()) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            _ => self
+                .to_string()
+                .to_lowercase()
+                .cmp(&other.to_string().to_lowercase()),
+        }
+    }
+
+    fn from_json(value: &Json) -> V {
+        match value {
+            Json::Number(n) => V::Num(n.as_f64().unwrap()),
+            Json::String(s) => V::Text(s.clone()),
+            Json::Bool(b) => V::Bool(*b),
+            _ => V::Text("".to_owned()),
+        }
+    }
+}
+
+impl std::fmt::Display for V {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            V::Num(n) if n.fract() == 0. && n.abs() < 1e15 => write!(f, "{}", *n as i64),
+            V::Num(n) => write!(f, "{n}"),
+            V::Text(s) => write!(f, "{s}"),
+            V::Bool(b) => write!(f, "{b}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Flow {
+    Next,
+    StopScript,
+    StopAll,
+}
+
+/// One target of a project, with the stage's variables and lists
+pub struct Vm<'a> {
+    blocks: &'a Map<String, Json>,
+    /// Name and value by id
+    vars: HashMap<String, (String, V)>,
+    lists: HashMap<String, (String, Vec<V>)>,
+    /// Arguments of the running custom blocks by name
+    args: Vec<HashMap<String, V>>,
+    steps: usize,
+    /// Everything `say` said
+    pub said: Vec<String>,
+    /// Proccodes of the custom blocks called, in order
+    pub calls: Vec<String>,
+}
+
+impl<'a> Vm<'a> {
+    pub fn new(project: &'a Json, target: usize) -> Vm<'a> {
+        let mut vm = Vm {
+            blocks: project["targets"][target]["blocks"].as_object().unwrap(),
+            vars: HashMap::new(),
+            lists: HashMap::new(),
+            args: vec![],
+            steps: 0,
+            said: vec![],
+            calls: vec![],
+        };
+        for target in [&project["targets"][0], &project["targets"][target]] {
+            for (id, var) in target["variables"].as_object().unwrap() {
+                let name = var[0].as_str().unwrap().to_owned();
+                vm.vars.insert(id.clone(), (name, V::from_json(&var[1])));
+            }
+            for (id, list) in target["lists"].as_object().unwrap() {
+                let name = list[0].as_str().unwrap().to_owned();
+                let items = list[1].as_array().unwrap().iter().map(V::from_json);
+                vm.lists.insert(id.clone(), (name, items.collect()));
+            }
+        }
+        vm
+    }
+
+    /// Run every `when green flag clicked` script one after the other
+    pub fn run_green_flag(&mut self) -> &mut Self {
+        let blocks = self.blocks;
+        let mut scripts: Vec<&str> = blocks
+            .iter()
+            .filter(|(_, block)| block["opcode"] == "event_whenflagclicked")
+            .map(|(id, _)| id.as_str())
+            .collect();
+        scripts.sort();
+        for id in scripts {
+            if self.run(blocks[id]["next"].as_str()) == Flow::StopAll {
+                break;
+            }
+        }
+        self
+    }
+
+    pub fn var(&self, name: &str) -> V {
+        self.vars
+            .values()
+            .find(|(var_name, _)| var_name == name)
+            .map(|(_, value)| value.clone())
+            .unwrap_or_else(|| panic!("no variable `{name}`"))
+    }
+
+    pub fn list(&self, name: &str) -> Vec<V> {
+        self.lists
+            .values()
+            .find(|(list_name, _)| list_name == name)
+            .map(|(_, items)| items.clone())
+            .unwrap_or_else(|| panic!("no list `{name}`"))
+    }
+
+    fn run(&mut self, mut next: Option<&str>) -> Flow {
+        let blocks = self.blocks;
+        while let Some(id) = next {
+            let block = &blocks[id];
+            let flow = self.exec(block);
+            if flow != Flow::Next {
+                return flow;
+            }
+            next = block["next"].as_str();
+        }
+        Flow::Next
+    }
+
+    fn substack(&mut self, block: &Json, name: &str) -> Flow {
+        self.run(block["inputs"][name][1].as_str())
+    }
+
+    fn exec(&mut self, block: &Json) -> Flow {
+        self.steps += 1;
+        assert!(self.steps < 100_000, "stuck in a loop");
+        let opcode = block["opcode"].as_str().unwrap();
+        match opcode {
+            "data_setvariableto" => {
+                let value = self.input(block, "VALUE");
+                self.var_mut(block).1 = value;
+            }
+            "data_changevariableby" => {
+                let by = self.input(block, "VALUE").num();
+                let var = &mut self.var_mut(block).1;
+                *var = V::Num(var.num() + by);
+            }
+            "data_addtolist" => {
+                let item = self.input(block, "ITEM");
+                self.list_mut(block).push(item);
+            }
+            "data_deleteoflist" => {
+                let index = self.input(block, "INDEX");
+                let list = self.list_mut(block);
+                if index.to_string() == "all" {
+                    list.clear();
+                } else if let Some(index) = list_index(&index, list.len()) {
+                    list.remove(index);
+                }
+            }
+            "data_deletealloflist" => self.list_mut(block).clear(),
+            "data_replaceitemoflist" => {
+                let index = self.input(block, "INDEX");
+                let item = self.input(block, "ITEM");
+                let list = self.list_mut(block);
+                if let Some(index) = list_index(&index, list.len()) {
+                    list[index] = item;
+                }
+            }
+            "looks_say" => {
+                let message = self.input(block, "MESSAGE").to_string();
+                self.said.push(message);
+            }
+            "control_repeat" => {
+                for _ in 0..self.input(block, "TIMES").num().round() as i64 {
+                    match self.substack(block, "SUBSTACK") {
+                        Flow::Next => {}
+                        flow => return flow,
+                    }
+                }
+            }
+            "control_repeat_until" | "control_while" => {
+                let until = opcode == "control_repeat_until";
+                while self.input(block, "CONDITION").truthy() != until {
+                    match self.substack(block, "SUBSTACK") {
+                        Flow::Next => {}
+                        flow => return flow,
+                    }
+                }
+            }
+            "control_forever" => loop {
+                match self.substack(block, "SUBSTACK") {
+                    Flow::Next => {}
+                    flow => return flow,
+                }
+            },
+            "control_if" => {
+                if self.input(block, "CONDITION").truthy() {
+                    return self.substack(block, "SUBSTACK");
+                }
+            }
+            "control_if_else" => {
+                return if self.input(block, "CONDITION").truthy() {
+                    self.substack(block, "SUBSTACK")
+                } else {
+                    self.substack(block, "SUBSTACK2")
+                };
+            }
+            "control_stop" => match block["fields"]["STOP_OPTION"][0].as_str().unwrap() {
+                "this script" => return Flow::StopScript,
+                "all" => return Flow::StopAll,
+                _ => {}
+            },
+            "procedures_call" => return self.call(block),
+            _ => panic!("can't run `{opcode}`"),
+        }
+        Flow::Next
+    }
+
+    fn call(&mut self, call: &Json) -> Flow {
+        let proccode = call["mutation"]["proccode"].as_str().unwrap();
+        self.calls.push(proccode.to_owned());
+        let blocks = self.blocks;
+        let (definition, prototype) = blocks
+            .values()
+            .filter(|block| block["opcode"] == "procedures_definition")
+            .map(|block| {
+                let prototype = block["inputs"]["custom_block"][1].as_str().unwrap();
+                (block, &blocks[prototype])
+            })
+            .find(|(_, prototype)| prototype["mutation"]["proccode"] == proccode)
+            .unwrap_or_else(|| panic!("`{proccode}` isn't defined"));
+        let ids = string_list(&prototype["mutation"]["argumentids"]);
+        let names = string_list(&prototype["mutation"]["argumentnames"]);
+        let mut args = HashMap::new();
+        for (id, name) in ids.iter().zip(names) {
+            args.insert(name, self.input(call, id));
+        }
+        self.args.push(args);
+        let flow = self.run(definition["next"].as_str());
+        self.args.pop();
+        match flow {
+            Flow::StopAll => Flow::StopAll,
+            _ => Flow::Next,
+        }
+    }
+
+    fn input(&mut self, block: &Json, name: &str) -> V {
+        match &block["inputs"][name][1] {
+            Json::String(id) => {
+                let blocks = self.blocks;
+                self.eval(&blocks[id])
+            }
+            Json::Array(literal) => match literal[0].as_u64().unwrap() {
+                12 => self.vars[literal[2].as_str().unwrap()].1.clone(),
+                13 => {
+                    let items = &self.lists[literal[2].as_str().unwrap()].1;
+                    V::Text(items.iter().map(V::to_string).collect::<Vec<_>>().join(" "))
+                }
+                _ => V::from_json(&literal[1]),
+            },
+            _ => V::Text("".to_owned()),
+        }
+    }
+
+    fn eval(&mut self, block: &Json) -> V {
+        let opcode = block["opcode"].as_str().unwrap();
+        match opcode {
+            "operator_add" => self.arithmetic(block, |a, b| a + b),
+            "operator_subtract" => self.arithmetic(block, |a, b| a - b),
+            "operator_multiply" => self.arithmetic(block, |a, b| a * b),
+            "operator_divide" => self.arithmetic(block, |a, b| a / b),
+            "operator_mod" => self.arithmetic(block, |a, b| a - b * (a / b).floor()),
+            "operator_gt" | "operator_lt" | "operator_equals" => {
+                let ordering = self
+                    .input(block, "OPERAND1")
+                    .compare(&self.input(block, "OPERAND2"));
+                V::Bool(match opcode {
+                    "operator_gt" => ordering.is_gt(),
+                    "operator_lt" => ordering.is_lt(),
+                    _ => ordering.is_eq(),
+                })
+            }
+            "operator_and" => V::Bool(
+                self.input(block, "OPERAND1").truthy() && self.input(block, "OPERAND2").truthy(),
+            ),
+            "operator_or" => V::Bool(
+                self.input(block, "OPERAND1").truthy() || self.input(block, "OPERAND2").truthy(),
+            ),
+            "operator_not" => V::Bool(!self.input(block, "OPERAND").truthy()),
+            "operator_join" => V::Text(format!(
+                "{}{}",
+                self.input(block, "STRING1"),
+                self.input(block, "STRING2")
+            )),
+            "data_variable" => self.var_mut(block).1.clone(),
+            "data_itemoflist" => {
+                let index = self.input(block, "INDEX");
+                let list = self.list_mut(block);
+                match list_index(&index, list.len()) {
+                    Some(index) => list[index].clone(),
+                    None => V::Text("".to_owned()),
+                }
+            }
+            "data_lengthoflist" => V::Num(self.list_mut(block).len() as f64),
+            "argument_reporter_string_number" | "argument_reporter_boolean" => {
+                let name = block["fields"]["VALUE"][0].as_str().unwrap();
+                self.args
+                    .last()
+                    .and_then(|args| args.get(name))
+                    .cloned()
+                    .unwrap_or(V::Num(0.))
+            }
+            _ => panic!("can't evaluate `{opcode}`"),
+        }
+    }
+
+    fn arithmetic(&mut self, block: &Json, f: fn(f64, f64) -> f64) -> V {
+        let a = self.input(block, "NUM1").num();
+        let b = self.input(block, "NUM2").num();
+        V::Num(f(a, b))
+    }
+
+    fn var_mut(&mut self, block: &Json) -> &mut (String, V) {
+        let id = block["fields"]["VARIABLE"][1].as_str().unwrap();
+        self.vars
+            .get_mut(id)
+            .unwrap_or_else(|| panic!("no variable with id `{id}`"))
+    }
+
+    fn list_mut(&mut self, block: &Json) -> &mut Vec<V> {
+        let id = block["fields"]["LIST"][1].as_str().unwrap();
+        &mut self
+            .lists
+            .get_mut(id)
+            .unwrap_or_else(|| panic!("no list with id `{id}`"))
+            .1
+    }
+}
+
+/// 0 based index from a Scratch list index, [`None`] when it's out of the list
+fn list_index(index: &V, len: usize) -> Option<usize> {
+    let index = match index.to_string().as_str() {
+        "last" => len,
+        _ => index.num().floor() as usize,
+    };
+    index.checked_sub(1).filter(|index| *index < len)
+}
+
+/// Mutations keep lists as JSON in a string
+fn string_list(value: &Json) -> Vec<String> {
+    let parsed;
+    let value = match value {
+        Json::String(s) => {
+            parsed = serde_json::from_str::<Json>(s).unwrap();
+            &parsed
+        }
+        value => value,
+    };
+    value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item.as_str().unwrap().to_owned())
+        .collect()
+}
@@ -0,0 +1,153 @@
+//! Control-flow helpers should run like the Rust they're named after.
+
+mod common;
+
+use common::{num, var, Bfb, Bib};
+use sb_itchy::{
+    blocks::{self, control_flow::*},
+    prelude::*,
+};
+use sb_sbity::value::Value;
+
+type Biv = BlockInputValue;
+
+fn get(name: &str) -> Bib {
+    Bib::stack(blocks::sprite_var(name))
+}
+
+fn say(name: &str) -> StackBuilder {
+    blocks::say(get(name))
+}
+
+/// What a sprite with `script` under a green flag says
+fn run(script: StackBuilder) -> Vec<String> {
+    let mut sprite = common::sprite();
+    for name in ["i", "x", "index", "flag", "item"] {
+        sprite
+            .target
+            .add_variable(name, VariableBuilder::new(Value::Number(0.into())));
+    }
+    sprite.target.add_list(
+        "items",
+        ListBuilder::new(vec![
+            Value::Text("a".into()),
+            Value::Text("b".into()),
+            Value::Text("c".into()),
+        ]),
+    );
+    common::run(sprite, script)
+}
+
+#[test]
+fn for_range_steps() {
+    let range = |from, to, step| run(for_range(var("i"), num(from), num(to), num(step), say("i")));
+    assert_eq!(range(1., 10., 3.), ["1", "4", "7", "10"]);
+    assert_eq!(range(5., 1., -2.), ["5", "3", "1"]);
+    assert_eq!(range(1., 0., 1.), Vec::<String>::new());
+    assert_eq!(range(0., 1., -1.), Vec::<String>::new());
+    assert_eq!(range(1., 5., 0.), Vec::<String>::new());
+    assert_eq!(range(3., 3., 0.), Vec::<String>::new());
+}
+
+#[test]
+fn for_each_item_in_order() {
+    let list = Bfb::new_with_kind("items".to_owned(), FieldKind::SpriteList);
+    assert_eq!(
+        run(for_each_item(
+            list,
+            var("item"),
+            var("index"),
+            say("index").next(say("item"))
+        )),
+        ["1", "a", "2", "b", "3", "c"]
+    );
+}
+
+#[test]
+fn repeat_while_checks_first() {
+    let below = |limit| Bib::stack(blocks::less_than(get("i"), num(limit)));
+    let count = blocks::change_var_by(var("i"), num(1.)).next(say("i"));
+    assert_eq!(run(repeat_while(below(3.), count.clone())), ["1", "2", "3"]);
+    assert_eq!(
+        run(blocks::set_var_to(var("i"), num(5.)).next(repeat_while(below(3.), count))),
+        Vec::<String>::new()
+    );
+}
+
+#[test]
+fn break_stops_the_loop() {
+    let body = blocks::change_var_by(var("i"), num(1.))
+        .next(say("i"))
+        .next(blocks::if_(
+            Bib::stack(blocks::equals(get("i"), num(3.))),
+            Some(Bib::stack(break_(var("flag")))),
+        ));
+    assert_eq!(
+        run(loop_with_break(var("flag"), None, body.clone())),
+        ["1", "2", "3"]
+    );
+
+    let below_two = Bib::stack(blocks::less_than(get("i"), num(2.)));
+    assert_eq!(
+        run(loop_with_break(var("flag"), Some(below_two), body)),
+        ["1", "2"]
+    );
+}
+
+/// Says what each of `values` matched
+fn matches(values: &[f64], cases: &[(&str, &str)], default: Option<&str>) -> Vec<String> {
+    let said = |text: &str| {
+        blocks::say(Bib::value(Biv::String {
+            value: text.to_owned().into(),
+        }))
+    };
+    let cases = cases
+        .iter()
+        .map(|(key, text)| (key.to_string(), said(text)))
+        .collect();
+    let matched = match_value(get("x"), cases, default.map(said)).unwrap();
+    let mut script = StackBuilder::new();
+    for value in values {
+        script = script
+            .next(blocks::set_var_to(var("x"), num(*value)))
+            .next(matched.clone());
+    }
+    run(script)
+}
+
+#[test]
+fn match_value_chain() {
+    let cases = [("1", "one"), ("2", "two"), ("1", "again")];
+    assert_eq!(
+        matches(&[1., 2., 5.], &cases, Some("other")),
+        ["one", "two", "other"]
+    );
+    assert_eq!(matches(&[5., 2.], &cases, None), ["two"]);
+}
+
+#[test]
+fn match_value_binary_search() {
+    let names = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    let keys: Vec<String> = (0..names.len()).map(|n| n.to_string()).collect();
+    // Out of order and with a duplicate that shouldn't win
+    let mut cases: Vec<(&str, &str)> = keys.iter().map(String::as_str).zip(names).rev().collect();
+    cases.push(("4", "again"));
+
+    let values: Vec<f64> = (-1..=10).map(f64::from).chain([2.5]).collect();
+    let mut expected = vec!["other"];
+    expected.extend(names);
+    expected.extend(["other", "other"]);
+    assert_eq!(matches(&values, &cases, Some("other")), expected);
+    assert_eq!(matches(&[-1., 4., 10.], &cases, None), ["four"]);
+}
+
+#[test]
+fn match_value_without_cases() {
+    assert!(match_value(get("x"), vec![], None).is_none());
+    assert_eq!(
+        run(match_value(get("x"), vec![], Some(say("x"))).unwrap()),
+        ["0"]
+    );
+}
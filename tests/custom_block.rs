@@ -0,0 +1,391 @@
+//! Custom block signatures and their round trip through the prototype's mutation,
+//! and custom blocks used as reporters.
+
+mod common;
+
+use common::{id_of, num, Bib, Vm};
+use sb_itchy::{blocks, prelude::*};
+use sb_sbity::{
+    block::BlockMutation,
+    value::{Value, ValueWithBool},
+};
+use serde_json::Value as Json;
+
+fn ty(signature: &str) -> CustomBlockTy {
+    CustomBlockTy::parse(signature, false).unwrap()
+}
+
+#[test]
+fn parse_forms() {
+    let percent = ty("move %s steps and jump %b");
+    let brackets = ty("move (steps) steps and jump <high>");
+    assert_eq!(percent.proccode(), "move %s steps and jump %b");
+    assert_eq!(percent.proccode(), brackets.proccode());
+    assert_eq!(percent.argumentnames(), ["arg1", "arg2"]);
+    assert_eq!(brackets.argumentnames(), ["steps", "high"]);
+
+    assert_eq!(ty("(a) > (b)").proccode(), "%s > %s");
+    assert_eq!(
+        CustomBlockTy::parse("oops (a", false),
+        Err(ParseSignatureError::Unclosed('('))
+    );
+    assert_eq!(
+        CustomBlockTy::parse("<>", false),
+        Err(ParseSignatureError::EmptyArgumentName)
+    );
+    assert_eq!(
+        CustomBlockTy::parse("  ", false),
+        Err(ParseSignatureError::Empty)
+    );
+}
+
+#[test]
+fn escaping() {
+    assert_eq!(ty("discount 50% (price)").proccode(), r"discount 50\% %s");
+    assert_eq!(ty(r"literally \%s").proccode(), r"literally \%s");
+    assert_eq!(ty(r"\(not an arg) %s").argumentnames(), ["arg1"]);
+    assert_eq!(
+        ty("say \t hi   there\n(what)").proccode(),
+        "say hi there %s"
+    );
+
+    let labels = CustomBlockTy::new(
+        vec![
+            CustomBlockInputType::Text("50%  off".into()),
+            CustomBlockInputType::string_or_number("price"),
+        ],
+        false,
+    );
+    assert_eq!(labels.proccode(), r"50\% off %s");
+}
+
+#[test]
+fn mutation_round_trip() {
+    let signatures = [
+        CustomBlockTy::new(
+            vec![
+                CustomBlockInputType::Text("discount 50%".into()),
+                CustomBlockInputType::StringOrNumber("price".into(), Value::Number(10.into())),
+                CustomBlockInputType::Text("for".into()),
+                CustomBlockInputType::StringOrNumber("who".into(), Value::Text("everyone".into())),
+                CustomBlockInputType::Boolean("now".into(), true),
+            ],
+            true,
+        ),
+        ty("%s and %b"),
+        ty(r"nothing \(to) see \%s here"),
+    ];
+    for signature in signatures {
+        let read = CustomBlockTy::from_mutation(&signature.define_mutation());
+        assert_eq!(read.as_ref(), Some(&signature));
+    }
+
+    // Only prototypes has the argument names
+    assert_eq!(
+        CustomBlockTy::from_mutation(&ty("call %s").call_mutation()),
+        None
+    );
+}
+
+#[test]
+fn defaults_in_project() {
+    let signature = CustomBlockTy::new(
+        vec![
+            CustomBlockInputType::Text("greet".into()),
+            CustomBlockInputType::StringOrNumber("who".into(), Value::Text("world".into())),
+            CustomBlockInputType::Boolean("loudly".into(), true),
+        ],
+        false,
+    );
+
+    let mut project = ProjectBuilder::default();
+    let mut sprite = common::sprite();
+    let greet = sprite
+        .target
+        .define_custom_block(signature.clone(), StackBuilder::new());
+    sprite.target.add_block_stack(greet.call::<&str, _>([]));
+    project.add_sprite(sprite);
+    let project = project.build_json(&mut vec![]);
+
+    let blocks = project["targets"][1]["blocks"].as_object().unwrap();
+    let find = |opcode: &str| {
+        blocks
+            .values()
+            .find(|block| block["opcode"] == opcode)
+            .unwrap_or_else(|| panic!("no `{opcode}`"))
+    };
+
+    let prototype = find("procedures_prototype");
+    let mutation: BlockMutation = serde_json::from_value(prototype["mutation"].clone()).unwrap();
+    let read = CustomBlockTy::from_mutation(&mutation.mutation_enum).unwrap();
+    assert_eq!(read, signature);
+    assert_eq!(
+        read.argumentdefaults(),
+        [
+            ValueWithBool::Text("world".into()),
+            ValueWithBool::Bool(true)
+        ]
+    );
+
+    let call = find("procedures_call");
+    let ids = signature.argumentids();
+    assert_eq!(call["inputs"][ids[0].clone().into_inner()][1][1], "world");
+    let not_id = call["inputs"][ids[1].clone().into_inner()][1]
+        .as_str()
+        .unwrap();
+    assert_eq!(blocks[not_id]["opcode"], "operator_not");
+}
+
+/// Sprite that says `said` when the green flag is clicked, after `define` added its custom blocks
+fn sprite_saying(define: impl FnOnce(&mut TargetBuilder), said: Bib) -> SpriteBuilder {
+    let mut sprite = common::sprite();
+    define(&mut sprite.target);
+    sprite
+        .target
+        .add_block_stack(blocks::when_flag_clicked().next(blocks::say(said)));
+    sprite
+}
+
+/// Ids of the variables the target's `data_setvariableto` blocks set
+fn set_variable_ids(target: &Json) -> Vec<String> {
+    target["blocks"]
+        .as_object()
+        .unwrap()
+        .values()
+        .filter(|block| block["opcode"] == "data_setvariableto")
+        .map(|block| block["fields"]["VARIABLE"][1].as_str().unwrap().to_owned())
+        .collect()
+}
+
+#[test]
+fn recursive_reporter() {
+    let fib = CustomBlockHandle::new(ty("fib (n)"));
+    let n = || Bib::stack(fib.arg("n").unwrap());
+    let call = |n: Bib| Bib::stack(fib.call([("n", n)]));
+    let body = blocks::if_(
+        Bib::stack(blocks::less_than(n(), num(2.))),
+        Some(Bib::stack(fib.ret(n()))),
+    )
+    .next(fib.ret(Bib::stack(blocks::add(
+        call(Bib::stack(blocks::sub(n(), num(1.)))),
+        call(Bib::stack(blocks::sub(n(), num(2.)))),
+    ))));
+
+    let mut project = ProjectBuilder::default();
+    project.add_sprite(sprite_saying(
+        |target| {
+            target.define_custom_block(ty("fib (n)"), body);
+        },
+        call(num(10.)),
+    ));
+    let project = project.build_json(&mut vec![]);
+
+    let mut vm = Vm::new(&project, 1);
+    vm.run_green_flag();
+    assert_eq!(vm.said, ["55"]);
+    // Every frame was removed
+    assert!(vm.list("(custom block results)").is_empty());
+}
+
+#[test]
+fn nested_calls() {
+    let double = CustomBlockHandle::new(ty("double (x)"));
+    let inc = CustomBlockHandle::new(ty("inc (x)"));
+    let x = |handle: &CustomBlockHandle| Bib::stack(handle.arg("x").unwrap());
+    let said = Bib::stack(inc.call([("x", Bib::stack(double.call([("x", num(3.))])))]));
+
+    let mut project = ProjectBuilder::default();
+    project.add_sprite(sprite_saying(
+        |target| {
+            let times_two = blocks::mul(x(&double), num(2.));
+            target.define_custom_block(ty("double (x)"), double.ret(Bib::stack(times_two)));
+            let plus_one = blocks::add(x(&inc), num(1.));
+            target.define_custom_block(ty("inc (x)"), inc.ret(Bib::stack(plus_one)));
+        },
+        said,
+    ));
+    let project = project.build_json(&mut vec![]);
+    let sprite = &project["targets"][1];
+    let script = script(sprite, green_flag);
+    let opcodes: Vec<&Json> = script.iter().map(|block| &block["opcode"]).collect();
+    assert_eq!(opcodes, ["procedures_call", "procedures_call", "looks_say"]);
+    assert_eq!(script[0]["mutation"]["proccode"], "double %s");
+    assert_eq!(script[1]["mutation"]["proccode"], "inc %s");
+
+    // inc gets what double returned, as a variable reporter put right in the input
+    let arg = &script[1]["inputs"]
+        .as_object()
+        .unwrap()
+        .values()
+        .next()
+        .unwrap()[1];
+    assert_eq!(arg[0], 12);
+    assert_eq!(arg[1], "double %s (return)");
+    assert_eq!(arg[2], id_of(sprite, "variables", "double %s (return)"));
+
+    let mut vm = Vm::new(&project, 1);
+    vm.run_green_flag();
+    assert_eq!(vm.calls, ["double %s", "inc %s"]);
+    assert_eq!(vm.said, ["7"]);
+}
+
+#[test]
+fn return_variable_per_target() {
+    let answer = CustomBlockHandle::new(ty("answer"));
+    let define = |target: &mut TargetBuilder, value: f64| {
+        target.define_custom_block(ty("answer"), answer.ret(num(value)));
+    };
+    let said = || Bib::stack(answer.call::<&str, _>([]));
+
+    let mut project = ProjectBuilder::default();
+    let mut stage = StageBuilder::default();
+    define(&mut stage.target, 1.);
+    stage
+        .target
+        .add_block_stack(blocks::when_flag_clicked().next(blocks::say(said())));
+    project.set_stage(stage);
+    project.add_sprite(sprite_saying(|target| define(target, 2.), said()));
+    let project = project.build_json(&mut vec![]);
+
+    let stage = &project["targets"][0];
+    let sprite = &project["targets"][1];
+    let stage_id = id_of(stage, "variables", "answer (return)");
+    let sprite_id = id_of(sprite, "variables", "answer (return)");
+    assert_ne!(stage_id, sprite_id);
+    assert_eq!(set_variable_ids(stage), [stage_id]);
+    assert_eq!(set_variable_ids(sprite), [sprite_id]);
+
+    assert_eq!(Vm::new(&project, 0).run_green_flag().said, ["1"]);
+    assert_eq!(Vm::new(&project, 1).run_green_flag().said, ["2"]);
+}
+
+/// Blocks under the first hat that `is_hat`, following `next`
+fn script<'a>(target: &'a Json, is_hat: impl Fn(&Json) -> bool) -> Vec<&'a Json> {
+    let blocks = target["blocks"].as_object().unwrap();
+    let hat = blocks.values().find(|block| is_hat(block)).unwrap();
+    let mut script = vec![];
+    let mut next = hat["next"].as_str();
+    while let Some(id) = next {
+        script.push(&blocks[id]);
+        next = blocks[id]["next"].as_str();
+    }
+    script
+}
+
+fn green_flag(block: &Json) -> bool {
+    block["opcode"] == "event_whenflagclicked"
+}
+
+#[test]
+fn locals_removed_on_every_exit() {
+    let mut signature = ty("pick (n)");
+    signature.add_local("tmp");
+    let pick = CustomBlockHandle::new(signature.clone());
+    let n = || Bib::stack(pick.arg("n").unwrap());
+    let below_zero = Bib::stack(blocks::less_than(n(), num(0.)));
+    let returns = pick
+        .set_local("tmp", n())
+        .unwrap()
+        .next(blocks::if_(below_zero, Some(Bib::stack(pick.ret(num(0.))))))
+        .next(pick.ret(pick.local("tmp").unwrap()));
+
+    let mut counter = ty("count");
+    counter.add_local("i");
+    let count = CustomBlockHandle::new(counter.clone());
+    let falls_off_the_end = count.set_local("i", num(1.)).unwrap();
+
+    let picks = blocks::join(
+        Bib::stack(pick.call([("n", num(-1.))])),
+        Bib::stack(pick.call([("n", num(5.))])),
+    );
+    let mut project = ProjectBuilder::default();
+    project.add_sprite(sprite_saying(
+        |target| {
+            target.define_custom_block(signature, returns);
+            target.define_custom_block(counter, falls_off_the_end);
+            target.add_block_stack(blocks::when_flag_clicked().next(count.call::<&str, _>([])));
+        },
+        Bib::stack(picks),
+    ));
+    let project = project.build_json(&mut vec![]);
+
+    let mut vm = Vm::new(&project, 1);
+    vm.run_green_flag();
+    assert_eq!(vm.said, ["05"]);
+    assert!(vm.list("pick %s (locals)").is_empty());
+    assert!(vm.list("count (locals)").is_empty());
+
+    // Nothing is left after the `stop this script` that ends it
+    let sprite = &project["targets"][1];
+    let blocks = sprite["blocks"].as_object().unwrap();
+    let definition = script(sprite, |block| {
+        let prototype = block["inputs"]["custom_block"][1].as_str();
+        prototype.is_some_and(|id| blocks[id]["mutation"]["proccode"] == "pick %s")
+    });
+    let opcodes: Vec<&Json> = definition.iter().map(|block| &block["opcode"]).collect();
+    assert_eq!(
+        opcodes,
+        [
+            "data_addtolist",
+            "data_replaceitemoflist",
+            "control_if",
+            "data_setvariableto",
+            "data_deleteoflist",
+            "control_stop",
+        ]
+    );
+    let in_if = blocks[definition[2]["inputs"]["SUBSTACK"][1].as_str().unwrap()]["next"]
+        .as_str()
+        .unwrap();
+    assert_eq!(blocks[in_if]["opcode"], "data_deleteoflist");
+}
+
+#[test]
+fn unknown_custom_block() {
+    let mut project = ProjectBuilder::default();
+    let mut sprite = common::sprite();
+    let missing = CustomBlockHandle::new(ty("missing (x)"));
+    let library = project.define_custom_block(ty("shared"), StackBuilder::new());
+    sprite.target.add_block_stack(
+        blocks::when_flag_clicked()
+            .next(missing.call([("x", num(1.))]))
+            .next(library.call::<&str, _>([])),
+    );
+    sprite
+        .target
+        .add_block_stack(blocks::define_custom_block("also missing"));
+    project.add_sprite(sprite);
+
+    assert_eq!(
+        Validator::new().validate_project(&project),
+        [
+            ValidationError::UnknownCustomBlock {
+                proccode: "missing %s".to_owned()
+            },
+            ValidationError::UnknownCustomBlock {
+                proccode: "also missing".to_owned()
+            },
+        ]
+    );
+    // Without the project, the library isn't there
+    assert_eq!(
+        Validator::new()
+            .validate_target(&project.sprite_builders[0].target)
+            .len(),
+        3
+    );
+
+    // Still builds
+    let project = project.build_json(&mut vec![]);
+    let sprite = &project["targets"][1];
+    let called = script(sprite, green_flag);
+    assert_eq!(called[0]["mutation"]["proccode"], "missing %s");
+    let prototypes: Vec<&Json> = sprite["blocks"]
+        .as_object()
+        .unwrap()
+        .values()
+        .filter(|block| block["opcode"] == "procedures_prototype")
+        .map(|block| &block["mutation"]["proccode"])
+        .collect();
+    assert!(prototypes.contains(&&Json::from("also missing")));
+}
@@ -0,0 +1,125 @@
+//! Monitors should point at the variables, lists and sprites they show.
+
+mod common;
+
+use common::id_of;
+use sb_itchy::prelude::*;
+use serde_json::Value as Json;
+
+fn project() -> ProjectBuilder {
+    let mut project = ProjectBuilder::default();
+    project.set_stage(common::stage());
+    project.add_sprite(common::sprite());
+    project
+}
+
+#[test]
+fn variable_monitor() {
+    let mut project = project();
+    let mut monitor = MonitorBuilder::variable("score");
+    monitor.set_slider(-10., 10., false);
+    project.add_monitor(monitor);
+    let project = project.build_json(&mut vec![]);
+
+    let monitor = &project["monitors"][0];
+    assert_eq!(
+        monitor["id"],
+        id_of(&project["targets"][0], "variables", "score")
+    );
+    assert_eq!(monitor["opcode"], "data_variable");
+    assert_eq!(monitor["mode"], "slider");
+    assert_eq!(monitor["params"]["VARIABLE"], "score");
+    assert_eq!(monitor["spriteName"], Json::Null);
+    assert_eq!(monitor["sliderMin"], -10.);
+    assert_eq!(monitor["sliderMax"], 10.);
+    assert_eq!(monitor["isDiscrete"], false);
+}
+
+#[test]
+fn list_monitor() {
+    let mut project = project();
+    let mut monitor = MonitorBuilder::list("items");
+    monitor.set_size(120., 240.).set_pos(Some(300.), Some(50.));
+    project.add_monitor(monitor);
+    let project = project.build_json(&mut vec![]);
+
+    let monitor = &project["monitors"][0];
+    assert_eq!(
+        monitor["id"],
+        id_of(&project["targets"][0], "lists", "items")
+    );
+    assert_eq!(monitor["opcode"], "data_listcontents");
+    assert_eq!(monitor["mode"], "list");
+    assert_eq!(monitor["params"]["LIST"], "items");
+    assert_eq!(monitor["width"], 120.);
+    assert_eq!(monitor["height"], 240.);
+    assert_eq!(monitor["x"], 300.);
+    assert_eq!(monitor["y"], 50.);
+    assert_eq!(monitor["sliderMin"], 0.);
+    assert_eq!(monitor["sliderMax"], 100.);
+    assert_eq!(monitor["isDiscrete"], true);
+}
+
+#[test]
+fn sprite_reporter_monitor() {
+    let mut project = project();
+    let mut monitor =
+        MonitorBuilder::reporter(MonitorReporter::CostumeNumberName("name".to_owned()));
+    monitor.set_sprite(Some("sprite1".to_owned()));
+    project.add_monitor(monitor);
+    project.add_monitor(MonitorBuilder::reporter(MonitorReporter::Timer));
+    let project = project.build_json(&mut vec![]);
+
+    let costume = &project["monitors"][0];
+    assert_eq!(costume["opcode"], "looks_costumenumbername");
+    assert_eq!(costume["params"]["NUMBER_NAME"], "name");
+    assert_eq!(costume["spriteName"], "sprite1");
+    let id = costume["id"].as_str().unwrap();
+    assert!(id.ends_with("_costumenumbername_name"), "{id}");
+    assert!(!id.starts_with('_'));
+
+    let timer = &project["monitors"][1];
+    assert_eq!(timer["id"], "timer");
+    assert_eq!(timer["spriteName"], Json::Null);
+}
+
+#[test]
+fn missing_things() {
+    let mut project = project();
+    let mut on_missing_sprite = MonitorBuilder::variable("score");
+    on_missing_sprite.set_sprite(Some("sprite2".to_owned()));
+    let mut global_on_sprite = MonitorBuilder::list("items");
+    global_on_sprite.set_sprite(Some("sprite1".to_owned()));
+    project
+        .add_monitor(on_missing_sprite)
+        .add_monitor(MonitorBuilder::variable("scroe"))
+        .add_monitor(global_on_sprite)
+        .add_monitor(MonitorBuilder::reporter(MonitorReporter::XPosition))
+        .add_monitor(MonitorBuilder::variable("score"));
+
+    assert_eq!(
+        Validator::new().validate_project(&project),
+        vec![
+            ValidationError::UnknownSprite {
+                sprite: "sprite2".to_owned()
+            },
+            ValidationError::UnknownVariable {
+                sprite: None,
+                name: "scroe".to_owned()
+            },
+            ValidationError::UnknownList {
+                sprite: Some("sprite1".to_owned()),
+                name: "items".to_owned()
+            },
+            ValidationError::MonitorWithoutSprite {
+                opcode: "motion_xposition".to_owned()
+            },
+        ]
+    );
+
+    // Building leaves them out instead of failing
+    let project = project.build_json(&mut vec![]);
+    let monitors = project["monitors"].as_array().unwrap();
+    assert_eq!(monitors.len(), 1);
+    assert_eq!(monitors[0]["params"]["VARIABLE"], "score");
+}
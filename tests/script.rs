@@ -0,0 +1,49 @@
+//! Scripts written as closures should run like the Rust that built them.
+
+mod common;
+
+use common::{var, Bib};
+use sb_itchy::{blocks, prelude::*};
+use sb_sbity::value::Value;
+
+/// What a sprite with `x` says with `script` under a green flag
+fn run(script: StackBuilder) -> Vec<String> {
+    let mut sprite = common::sprite();
+    sprite
+        .target
+        .add_variable("x", VariableBuilder::new(Value::Number(0.into())));
+    common::run(sprite, script)
+}
+
+#[test]
+fn rust_control_flow_in_closures() {
+    let x = || blocks::sprite_var("x");
+    let counting = |loud: bool| {
+        script(|s| {
+            for step in 1..=3 {
+                s.change_var(var("x"), step);
+                if loud {
+                    s.say(x());
+                }
+            }
+            s.if_else(
+                x().gt(5).and(x().lt(7)),
+                |s| s.say(x().mul(2).join("!")),
+                |s| s.say("no"),
+            );
+            s.if_(x().equals(6).not(), |s| s.say("unreachable"));
+        })
+    };
+    assert_eq!(run(counting(true)), ["1", "3", "6", "12!"]);
+    assert_eq!(run(counting(false)), ["12!"]);
+}
+
+#[test]
+fn operators_put_self_on_the_left() {
+    let said = run(script(|s| {
+        s.say(blocks::sprite_var("x").sub(3));
+        s.say(Bib::from(10).div(4));
+        s.say(Bib::from(7).modulo(3).add(0.5));
+    }));
+    assert_eq!(said, ["-3", "2.5", "1.5"]);
+}